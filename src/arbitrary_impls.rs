@@ -0,0 +1,78 @@
+//! [`Arbitrary`] implementations for the lists in this crate.
+//!
+//! Each list is built from a `Vec` of elements, so that iterating over the list
+//! yields the elements in the same order as the `Vec`, like the [`strategy`](crate::strategy) module.
+use alloc::vec::Vec;
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+
+#[cfg(feature = "stack")]
+impl<'a, T: Arbitrary<'a>> Arbitrary<'a> for crate::stack::List<T> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Vec::arbitrary(u).map(Self::from)
+    }
+
+    fn arbitrary_take_rest(u: Unstructured<'a>) -> Result<Self> {
+        Vec::arbitrary_take_rest(u).map(Self::from)
+    }
+}
+
+#[cfg(any(feature = "persistent", feature = "persistent_arc"))]
+macro_rules! persistent_arbitrary {
+    ($module: ident) => {
+        impl<'a, T: Arbitrary<'a>> Arbitrary<'a> for crate::$module::List<T> {
+            fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+                Vec::arbitrary(u).map(Self::from)
+            }
+
+            fn arbitrary_take_rest(u: Unstructured<'a>) -> Result<Self> {
+                Vec::arbitrary_take_rest(u).map(Self::from)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "persistent")]
+persistent_arbitrary!{persistent}
+
+#[cfg(feature = "persistent_arc")]
+persistent_arbitrary!{persistent_arc}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use ::arbitrary::{Arbitrary, Unstructured};
+
+    const BYTES: &[u8] = &[1, 10, 1, 20, 1, 30, 0, 7];
+
+    /// Checks that `List` generates the same elements as `Vec`, in the same order.
+    macro_rules! check_against_vec {
+        ($list: ty) => {
+            let vec = Vec::<u8>::arbitrary(&mut Unstructured::new(BYTES)).unwrap();
+            let list = <$list>::arbitrary(&mut Unstructured::new(BYTES)).unwrap();
+            assert!(!vec.is_empty());
+            assert!(list.iter().eq(&vec));
+
+            let vec = Vec::<u8>::arbitrary_take_rest(Unstructured::new(BYTES)).unwrap();
+            let list = <$list>::arbitrary_take_rest(Unstructured::new(BYTES)).unwrap();
+            assert!(list.iter().eq(&vec));
+        };
+    }
+
+    #[test]
+    #[cfg(feature = "stack")]
+    fn stack() {
+        check_against_vec!(crate::stack::List<u8>);
+    }
+
+    #[test]
+    #[cfg(feature = "persistent")]
+    fn persistent() {
+        check_against_vec!(crate::persistent::List<u8>);
+    }
+
+    #[test]
+    #[cfg(feature = "persistent_arc")]
+    fn persistent_arc() {
+        check_against_vec!(crate::persistent_arc::List<u8>);
+    }
+}
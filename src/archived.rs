@@ -0,0 +1,416 @@
+//! [`rkyv`] archiving for the lists in this crate.
+//!
+//! [`stack::List`](crate::stack::List), [`persistent::List`](crate::persistent::List)
+//! and [`persistent_arc::List`](crate::persistent_arc::List) all archive to an [`ArchivedList`],
+//! which can be read directly from the archived bytes, for example from a memory-mapped file.
+//!
+//! Each archived node holds a relative pointer to the next one, so the bytes can be moved freely.
+//! Persistent lists that share a tail are archived with the tail only once,
+//! as long as they are serialized with the same serializer, such as in one `Vec` of lists.
+//!
+//! Serializing and validating both walk the list in a loop, so long lists do not overflow the stack.
+use alloc::vec::Vec;
+use core::alloc::Layout;
+use core::any::TypeId;
+use core::fmt;
+#[cfg(any(feature = "persistent", feature = "persistent_arc"))]
+use core::ptr;
+use ::rkyv::bytecheck::CheckBytes;
+use ::rkyv::erased::ErasedPtr;
+use ::rkyv::munge::munge;
+use ::rkyv::rancor::{fail, Fallible, Source};
+use ::rkyv::ser::{Writer, WriterExt};
+#[cfg(any(feature = "persistent", feature = "persistent_arc"))]
+use ::rkyv::ser::{sharing::SharingState, Sharing};
+use ::rkyv::validation::shared::ValidationState;
+use ::rkyv::validation::{ArchiveContext, SharedContext};
+use ::rkyv::{Archive, Place, Portable, RelPtr, Serialize};
+
+/// An archived list, which can be read without deserializing it.
+///
+/// This is the archived form of every list in this crate that supports [`rkyv`].
+/// See the [module-level documentation](self) for more.
+#[derive(Portable)]
+#[repr(transparent)]
+pub struct ArchivedList<T> {
+    head: RelPtr<ArchivedNode<T>>,
+}
+
+#[derive(Portable)]
+#[repr(C)]
+struct ArchivedNode<T> {
+    elem: T,
+    next: ArchivedList<T>,
+}
+
+impl<T> ArchivedList<T> {
+    /// Returns whether the list is empty.
+    pub fn is_empty(&self) -> bool {
+        self.head.is_invalid()
+    }
+
+    /// Returns the length of the list.
+    ///
+    /// This walks the whole list.
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Returns a reference to the first element in the list, if it exists.
+    pub fn head(&self) -> Option<&T> {
+        self.node().map(|node| &node.elem)
+    }
+
+    /// Returns the list with the first node removed.
+    ///
+    /// If the list is empty, this returns the list itself.
+    pub fn tail(&self) -> &Self {
+        self.node().map_or(self, |node| &node.next)
+    }
+
+    /// Creates an iterator that yields shared references to each element in the list.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { next: self.node() }
+    }
+
+    #[allow(unsafe_code)]
+    fn node(&self) -> Option<&ArchivedNode<T>> {
+        if self.head.is_invalid() {
+            None
+        } else {
+            // SAFETY: An `ArchivedList` is only ever read from an archive that was either validated,
+            // which checks every node, or that the caller promised is valid.
+            Some(unsafe { &*self.head.as_ptr() })
+        }
+    }
+
+    /// Writes an archived list whose first node is at `resolver`'s position.
+    fn resolve(resolver: ListResolver, out: Place<Self>) {
+        munge!(let ArchivedList { head } = out);
+        match resolver.head {
+            Some(pos) => RelPtr::emplace(pos, head),
+            None => RelPtr::emplace_invalid(head),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ArchivedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a ArchivedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+/// An iterator that yields shared references to the elements of an [`ArchivedList`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Iter<'a, T> {
+    next: Option<&'a ArchivedNode<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.map(|node| {
+            self.next = node.next.node();
+            &node.elem
+        })
+    }
+}
+
+/// The resolver for an [`ArchivedList`].
+#[derive(Clone, Copy)]
+pub struct ListResolver {
+    /// The position of the first node, or `None` if the list is empty.
+    head: Option<usize>,
+}
+
+/// A node to be archived, borrowing its element from the list.
+struct NodeRef<'a, T> {
+    elem: &'a T,
+}
+
+impl<T: Archive> Archive for NodeRef<'_, T> {
+    type Archived = ArchivedNode<T::Archived>;
+    type Resolver = (T::Resolver, ListResolver);
+
+    fn resolve(&self, (elem_resolver, next_resolver): Self::Resolver, out: Place<Self::Archived>) {
+        munge!(let ArchivedNode { elem, next } = out);
+        self.elem.resolve(elem_resolver, elem);
+        ArchivedList::resolve(next_resolver, next);
+    }
+}
+
+/// Serializes a node for each element, from last to first, in front of the node at `next`.
+/// Returns the position of each node, in the same order as `elems`.
+fn serialize_nodes<T, S>(elems: &[&T], mut next: Option<usize>, serializer: &mut S) -> Result<Vec<usize>, S::Error>
+where
+    T: Serialize<S>,
+    S: Fallible + Writer + ?Sized,
+{
+    let mut positions = Vec::with_capacity(elems.len());
+    for &elem in elems.iter().rev() {
+        let resolver = (elem.serialize(serializer)?, ListResolver { head: next });
+        serializer.align_for::<ArchivedNode<T::Archived>>()?;
+        // SAFETY: `resolver` was just made by serializing `elem`,
+        // and the serializer was just aligned for the node.
+        #[allow(unsafe_code)]
+        let pos = unsafe { serializer.resolve_aligned(&NodeRef { elem }, resolver)? };
+        positions.push(pos);
+        next = Some(pos);
+    }
+    positions.reverse();
+    Ok(positions)
+}
+
+#[cfg(feature = "stack")]
+impl<T: Archive, A: crate::stack::Allocator> Archive for crate::stack::List<T, A> {
+    type Archived = ArchivedList<T::Archived>;
+    type Resolver = ListResolver;
+
+    fn resolve(&self, resolver: ListResolver, out: Place<Self::Archived>) {
+        ArchivedList::resolve(resolver, out);
+    }
+}
+
+#[cfg(feature = "stack")]
+impl<T, S, A> Serialize<S> for crate::stack::List<T, A>
+where
+    T: Serialize<S>,
+    S: Fallible + Writer + ?Sized,
+    A: crate::stack::Allocator,
+{
+    fn serialize(&self, serializer: &mut S) -> Result<ListResolver, S::Error> {
+        let elems: Vec<&T> = self.iter().collect();
+        let positions = serialize_nodes(&elems, None, serializer)?;
+        Ok(ListResolver { head: positions.first().copied() })
+    }
+}
+
+#[derive(Debug)]
+struct CyclicListError;
+
+impl fmt::Display for CyclicListError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("encountered a cyclic list")
+    }
+}
+
+impl core::error::Error for CyclicListError {}
+
+#[cfg(any(feature = "persistent", feature = "persistent_arc"))]
+macro_rules! persistent_archive {
+    ($module: ident) => {
+        impl<T: Archive> Archive for crate::$module::List<T> {
+            type Archived = ArchivedList<T::Archived>;
+            type Resolver = ListResolver;
+
+            fn resolve(&self, resolver: ListResolver, out: Place<Self::Archived>) {
+                ArchivedList::resolve(resolver, out);
+            }
+        }
+
+        impl<T, S> Serialize<S> for crate::$module::List<T>
+        where
+            T: Serialize<S>,
+            S: Fallible + Writer + Sharing + ?Sized,
+            S::Error: Source,
+        {
+            /// Serializes the nodes of the list that were not already serialized by another list.
+            fn serialize(&self, serializer: &mut S) -> Result<ListResolver, S::Error> {
+                // Each element lives in its own node, so its address identifies the node.
+                let mut elems = Vec::new();
+                let mut tail = None;
+                for elem in self {
+                    match serializer.start_sharing(ptr::from_ref(elem) as usize) {
+                        SharingState::Started => elems.push(elem),
+                        SharingState::Pending => fail!(CyclicListError),
+                        SharingState::Finished(pos) => {
+                            tail = Some(pos);
+                            break;
+                        }
+                    }
+                }
+
+                let positions = serialize_nodes(&elems, tail, serializer)?;
+                for (&elem, &pos) in elems.iter().zip(&positions) {
+                    serializer.finish_sharing(ptr::from_ref(elem) as usize, pos)?;
+                }
+                Ok(ListResolver { head: positions.first().copied().or(tail) })
+            }
+        }
+    };
+}
+
+#[cfg(feature = "persistent")]
+persistent_archive!{persistent}
+
+#[cfg(feature = "persistent_arc")]
+persistent_archive!{persistent_arc}
+
+// SAFETY: Every node is checked to be in bounds and aligned before it is read,
+// and every element is checked before `check_bytes` returns.
+#[allow(unsafe_code)]
+unsafe impl<T, C> CheckBytes<C> for ArchivedList<T>
+where
+    T: CheckBytes<C> + 'static,
+    C: Fallible + ArchiveContext + SharedContext + ?Sized,
+    C::Error: Source,
+{
+    /// Checks the nodes from first to last, and then their elements from last to first,
+    /// which is the order they were serialized in.
+    ///
+    /// Nodes that were already checked as part of another list are not checked again.
+    unsafe fn check_bytes(value: *const Self, context: &mut C) -> Result<(), C::Error> {
+        let type_id = TypeId::of::<ArchivedNode<T>>();
+        let mut nodes = Vec::new();
+        let mut list = value;
+
+        loop {
+            // SAFETY: `list` is either `value`, which the caller guarantees is aligned and
+            // points to enough bytes, or the `next` field of a node that was checked below.
+            let head = unsafe { &(*list).head };
+            if head.is_invalid() {
+                break;
+            }
+
+            let node = head.as_ptr_wrapping();
+            match context.start_shared(type_id, ErasedPtr::new(node.cast_mut()), |_, _| true)? {
+                ValidationState::Started => {
+                    context.check_subtree_ptr(node.cast(), &Layout::new::<ArchivedNode<T>>())?;
+                    // SAFETY: `check_subtree_ptr` checked that the node is inside the archive.
+                    let range = unsafe { context.push_subtree_range(node.cast(), node.add(1).cast())? };
+                    nodes.push((node, range));
+                    // SAFETY: The node is in bounds and aligned.
+                    list = unsafe { &raw const (*node).next };
+                }
+                ValidationState::Pending => fail!(CyclicListError),
+                ValidationState::Finished => break,
+            }
+        }
+
+        for (node, range) in nodes.into_iter().rev() {
+            // SAFETY: The node is in bounds and aligned, and so is its element.
+            unsafe {
+                T::check_bytes(&raw const (*node).elem, context)?;
+                context.pop_subtree_range(range)?;
+            }
+            context.finish_shared(type_id, ErasedPtr::new(node.cast_mut()))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ArchivedList;
+    #[cfg(any(feature = "stack", feature = "persistent"))]
+    use alloc::vec::Vec;
+    use ::rkyv::rancor::Error;
+    #[cfg(feature = "stack")]
+    use ::rkyv::util::AlignedVec;
+    use ::rkyv::Archived;
+
+    #[test]
+    #[cfg(feature = "stack")]
+    fn stack() {
+        let list: crate::stack::List<u32> = (0..100).collect();
+        let bytes = ::rkyv::to_bytes::<Error>(&list).unwrap();
+        let archived = ::rkyv::access::<ArchivedList<Archived<u32>>, Error>(&bytes).unwrap();
+
+        assert_eq!(archived.len(), 100);
+        assert!(archived.iter().map(|elem| elem.to_native()).eq(list.iter().copied()));
+        assert_eq!(archived.head().map(|elem| elem.to_native()), list.peek().copied());
+        assert!(archived.tail().iter().map(|elem| elem.to_native()).eq(list.iter().skip(1).copied()));
+
+        let empty = ::rkyv::to_bytes::<Error>(&crate::stack::List::<u32>::new()).unwrap();
+        let archived = ::rkyv::access::<ArchivedList<Archived<u32>>, Error>(&empty).unwrap();
+        assert!(archived.is_empty());
+        assert_eq!(archived.head(), None);
+        assert!(archived.tail().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "stack")]
+    fn long_list() {
+        // Long enough to overflow the stack if anything recursed once per node.
+        let list: crate::stack::List<u8> = (0..=u8::MAX).cycle().take(1_000_000).collect();
+        let bytes = ::rkyv::to_bytes::<Error>(&list).unwrap();
+        let archived = ::rkyv::access::<ArchivedList<u8>, Error>(&bytes).unwrap();
+        assert!(archived.iter().eq(list.iter()));
+    }
+
+    #[test]
+    #[cfg(feature = "stack")]
+    fn nested() {
+        let list: crate::stack::List<Vec<u16>> = (0..10).map(|len| (0..len).collect()).collect();
+        let bytes = ::rkyv::to_bytes::<Error>(&list).unwrap();
+        let archived = ::rkyv::access::<ArchivedList<Archived<Vec<u16>>>, Error>(&bytes).unwrap();
+        assert!(archived.iter().map(::rkyv::vec::ArchivedVec::len).eq(list.iter().map(Vec::len)));
+    }
+
+    #[test]
+    #[cfg(feature = "stack")]
+    fn invalid() {
+        let list: crate::stack::List<u32> = (0..10).collect();
+        let bytes = ::rkyv::to_bytes::<Error>(&list).unwrap();
+
+        // Cut off the first nodes, so the root points past the end.
+        let mut truncated = AlignedVec::<16>::new();
+        truncated.extend_from_slice(&bytes[bytes.len() / 2..]);
+        assert!(::rkyv::access::<ArchivedList<Archived<u32>>, Error>(&truncated).is_err());
+
+        // Make the root point at itself.
+        let mut cyclic = AlignedVec::<16>::new();
+        cyclic.extend_from_slice(&[0; 4]);
+        assert!(::rkyv::access::<ArchivedList<Archived<u32>>, Error>(&cyclic).is_err());
+    }
+
+    #[cfg(feature = "persistent")]
+    fn elem_ptrs<T>(list: &ArchivedList<T>) -> Vec<*const T> {
+        list.iter().map(core::ptr::from_ref).collect()
+    }
+
+    #[test]
+    #[cfg(feature = "persistent")]
+    fn persistent_shared_tails() {
+        use crate::persistent::List;
+
+        let base: List<u32> = (0..50).collect();
+        let lists = alloc::vec![base.prepend(100), base.tail().prepend(200), List::new(), base.clone()];
+        let bytes = ::rkyv::to_bytes::<Error>(&lists).unwrap();
+        let archived = ::rkyv::access::<Archived<Vec<List<u32>>>, Error>(&bytes).unwrap();
+
+        for (list, archived) in lists.iter().zip(archived.iter()) {
+            assert!(archived.iter().map(|elem| elem.to_native()).eq(list.iter().copied()));
+        }
+
+        // The base list's nodes are archived once, and every list uses them.
+        let base_nodes = elem_ptrs(archived[0].tail());
+        assert_eq!(elem_ptrs(&archived[3]), base_nodes);
+        assert_eq!(elem_ptrs(archived[1].tail()), base_nodes[1..]);
+        let node_size = core::mem::size_of::<super::ArchivedNode<Archived<u32>>>();
+        assert!(bytes.len() < 60 * node_size);
+    }
+
+    #[test]
+    #[cfg(feature = "persistent_arc")]
+    fn persistent_arc() {
+        use crate::persistent_arc::List;
+
+        let list: List<i64> = (0..20).collect();
+        let bytes = ::rkyv::to_bytes::<Error>(&list).unwrap();
+        let archived = ::rkyv::access::<ArchivedList<Archived<i64>>, Error>(&bytes).unwrap();
+        assert!(archived.iter().map(|elem| elem.to_native()).eq(list.iter().copied()));
+        assert_eq!(archived.len(), list.len());
+        assert_eq!(archived.head().map(|elem| elem.to_native()), list.head().copied());
+    }
+}
@@ -0,0 +1,44 @@
+//! A persistent list with `O(1)` appending, using `Rc`.
+//!
+//! This is a catenable list, as described by Chris Okasaki.
+//! Each list is a tree whose elements are in preorder,
+//! and appending two lists just makes the second one the last child of the first.
+//! The children of each node are kept in a [`persistent_queue::Queue`],
+//! which makes `append`, `prepend` and `push_back` `O(1)`.
+//! The tail of a list links the children of its root together lazily:
+//! only the first child is linked onto the rest of them, which are themselves
+//! only linked the first time they are needed, and each link is done at most once.
+//! This keeps `tail` amortized `O(1)`, even when the same version of a list is used more than once.
+//!
+//! Because this list uses [`Rc`], it is not thread-safe.
+//! If you need this to be thread-safe, enable the `catenable_arc`
+//! crate feature, and use the [`catenable_arc`] module instead.
+//! That module has the exact same API as this one.
+//!
+//! [`persistent_queue::Queue`]: crate::persistent_queue::Queue
+//! [`catenable_arc`]: crate::catenable_arc
+use alloc::rc::Rc;
+use crate::persistent_queue::{Queue, Iter as QueueIter};
+
+version!{0, 1, 0;
+    thread_safe: false,
+    persistent: true,
+    complexity: {
+        prepend: "O(1)",
+        push_back: "O(1)",
+        append: "O(1)",
+        head: "O(1)",
+        tail: "amortized O(1)",
+        len: "O(1)",
+    },
+}
+
+make_catenable_list!{Rc;
+/// A persistent list with `O(1)` appending that is not thread safe.
+/// See the [module-level documentation](self) for more.
+}
+
+/// A list that is only linked together the first time it is needed.
+type Thunk<T> = crate::thunk_common::rc::Thunk<List<T>, LinkAll<T>>;
+
+catenable_tests!{}
@@ -0,0 +1,55 @@
+//! A persistent list with `O(1)` appending, using `Arc`.
+//!
+//! This is a catenable list, as described by Chris Okasaki.
+//! Each list is a tree whose elements are in preorder,
+//! and appending two lists just makes the second one the last child of the first.
+//! The children of each node are kept in a [`persistent_queue_arc::Queue`],
+//! which makes `append`, `prepend` and `push_back` `O(1)`.
+//! The tail of a list links the children of its root together lazily:
+//! only the first child is linked onto the rest of them, which are themselves
+//! only linked the first time they are needed, and each link is done at most once.
+//! This keeps `tail` amortized `O(1)`, even when the same version of a list is used more than once.
+//!
+//! Because this list uses [`Arc`], it is thread-safe,
+//! but incurs some additional overhead.
+//! If two threads need the same list linked at once, one of them links it
+//! while the other spins until it is done.
+//! If you do not need your list to be thread-safe,
+//! enable the `catenable` feature, and use the [`catenable`] module instead.
+//! That module has the exact same API as this one.
+//!
+//! [`persistent_queue_arc::Queue`]: crate::persistent_queue_arc::Queue
+//! [`catenable`]: crate::catenable
+use alloc::sync::Arc;
+use crate::persistent_queue_arc::{Queue, Iter as QueueIter};
+
+version!{0, 1, 0;
+    thread_safe: true,
+    persistent: true,
+    complexity: {
+        prepend: "O(1)",
+        push_back: "O(1)",
+        append: "O(1)",
+        head: "O(1)",
+        tail: "amortized O(1)",
+        len: "O(1)",
+    },
+}
+
+make_catenable_list!{Arc;
+/// A persistent list with `O(1)` appending that is thread safe.
+/// See the [module-level documentation](self) for more.
+}
+
+/// A list that is only linked together the first time it is needed.
+type Thunk<T> = crate::thunk_common::arc::Thunk<List<T>, LinkAll<T>>;
+
+#[allow(dead_code)]
+fn assert_properties() {
+    fn is_thread_safe<T: Send + Sync>() {}
+
+    is_thread_safe::<List<i32>>();
+    is_thread_safe::<Iter<i32>>();
+}
+
+catenable_tests!{}
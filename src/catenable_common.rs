@@ -0,0 +1,380 @@
+macro_rules! make_catenable_list {
+    ($ptr: ident; $(#[$listdoc:meta])*) => {
+        $(#[$listdoc])*
+        pub struct List<T> {
+            root: Option<$ptr<Node<T>>>,
+            len: usize,
+        }
+
+        /// A tree whose elements are in preorder.
+        struct Node<T> {
+            elem: $ptr<T>,
+            children: Queue<Child<T>>,
+            // The children linked together, which is only done the first time it is needed.
+            tail: Thunk<T>,
+        }
+
+        /// A non-empty list in the children of a node, which may not have been evaluated yet.
+        struct Child<T> {
+            len: usize,
+            list: Thunk<T>,
+        }
+
+        /// A suspended call to link a queue of children together,
+        /// with `len` elements between all of them.
+        struct LinkAll<T> {
+            children: Queue<Child<T>>,
+            len: usize,
+        }
+
+        catenable_list_impl!{$ptr}
+        make_catenable_iter!{}
+    };
+}
+
+macro_rules! catenable_list_impl {
+    ($ptr: ident) => {
+        impl<T> List<T> {
+            /// Creates a new list.
+            pub fn new() -> Self {
+                Self { root: None, len: 0 }
+            }
+
+            fn singleton(elem: T) -> Self {
+                Self {
+                    root: Some($ptr::new(Node {
+                        elem: $ptr::new(elem),
+                        children: Queue::new(),
+                        tail: Thunk::ready(Self::new()),
+                    })),
+                    len: 1,
+                }
+            }
+
+            /// Returns whether the list is empty.
+            pub fn is_empty(&self) -> bool {
+                self.root.is_none()
+            }
+
+            /// Returns the length of the list.
+            pub fn len(&self) -> usize {
+                self.len
+            }
+
+            /// Returns a reference to the first element in the list, if it exists.
+            pub fn head(&self) -> Option<&T> {
+                self.root.as_ref().map(|node| &*node.elem)
+            }
+
+            /// Returns the list with the first element removed.
+            ///
+            /// This is amortized `O(1)`, and calling it again on the same list is `O(1)`.
+            pub fn tail(&self) -> Self {
+                self.root.as_ref().map_or_else(Self::new, |root| root.tail.force(LinkAll::run).clone())
+            }
+
+            /// Returns the first element of the list along with the rest of the list, if it is not empty.
+            ///
+            /// This is amortized `O(1)`.
+            pub fn uncons(&self) -> Option<(&T, Self)> {
+                self.head().map(|head| (head, self.tail()))
+            }
+
+            /// Prepends an element to the front of the list, returning the new list.
+            pub fn prepend(&self, elem: T) -> Self {
+                Self::singleton(elem).append(self)
+            }
+
+            /// Appends an element to the back of the list, returning the new list.
+            pub fn push_back(&self, elem: T) -> Self {
+                self.append(&Self::singleton(elem))
+            }
+
+            /// Returns a new list with the elements of `self` followed by the elements of `other`.
+            ///
+            /// This is `O(1)`, and shares all of the nodes of both lists.
+            pub fn append(&self, other: &Self) -> Self {
+                if self.is_empty() {
+                    other.clone()
+                } else if other.is_empty() {
+                    self.clone()
+                } else {
+                    self.link(other.clone())
+                }
+            }
+
+            /// Adds `other` as the last child of the root of `self`.
+            /// Both lists must be non-empty.
+            fn link(&self, other: Self) -> Self {
+                self.link_child(Child { len: other.len, list: Thunk::ready(other) })
+            }
+
+            fn link_child(&self, child: Child<T>) -> Self {
+                let root = self.root.as_ref().expect("linking onto an empty list");
+                let len = self.len + child.len;
+                let children = root.children.push_back(child);
+                Self {
+                    len,
+                    root: Some($ptr::new(Node {
+                        elem: root.elem.clone(),
+                        tail: Thunk::new(LinkAll { children: children.clone(), len: len - 1 }),
+                        children,
+                    })),
+                }
+            }
+
+            /// Creates an iterator that yields shared references to each element in the list.
+            pub fn iter(&self) -> Iter<'_, T> {
+                Iter {
+                    next: self.root.as_deref(),
+                    children: alloc::vec::Vec::new(),
+                    len: self.len,
+                }
+            }
+        }
+
+        impl<T> LinkAll<T> {
+            /// Links the first child onto the rest of them,
+            /// leaving the rest to be linked the first time they are needed.
+            fn run(self) -> List<T> {
+                let Some(first) = self.children.peek() else {
+                    return List::new();
+                };
+                let list = first.list.force(LinkAll::run).clone();
+                let rest = self.children.without_front();
+                if rest.is_empty() {
+                    return list;
+                }
+
+                let len = self.len - first.len;
+                list.link_child(Child { len, list: Thunk::new(LinkAll { children: rest, len }) })
+            }
+        }
+
+        impl<T> Clone for List<T> {
+            fn clone(&self) -> Self {
+                Self { root: self.root.clone(), len: self.len }
+            }
+        }
+
+        impl<T: core::fmt::Debug> core::fmt::Debug for List<T> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_list().entries(self).finish()
+            }
+        }
+
+        impl<T> Default for List<T> {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl<T> FromIterator<T> for List<T> {
+            /// Creates a list with the elements of the iterator in the same order.
+            fn from_iter<I: IntoIterator<Item=T>>(iter: I) -> Self {
+                let mut list = List::new();
+                for elem in iter {
+                    list = list.push_back(elem);
+                }
+                list
+            }
+        }
+
+        impl<'a, T> IntoIterator for &'a List<T> {
+            type Item = &'a T;
+            type IntoIter = Iter<'a, T>;
+
+            fn into_iter(self) -> Iter<'a, T> {
+                self.iter()
+            }
+        }
+
+        impl<T> Drop for List<T> {
+            fn drop(&mut self) {
+                // Lists can be nested arbitrarily deep, so flatten them
+                // onto stacks instead of letting them drop recursively.
+                let mut nodes: alloc::vec::Vec<$ptr<Node<T>>> = self.root.take().into_iter().collect();
+                let mut thunks: alloc::vec::Vec<Thunk<T>> = alloc::vec::Vec::new();
+                loop {
+                    if let Some(node) = nodes.pop() {
+                        let Some(Node { elem: _, children, tail }) = $ptr::into_inner(node) else { continue };
+                        thunks.push(tail);
+                        // Only the children that nothing else shares are about to be dropped.
+                        children.drain_unique(|child| thunks.push(child.list));
+                    } else if let Some(thunk) = thunks.pop() {
+                        let (list, link_all) = thunk.into_parts();
+                        nodes.extend(list.and_then(|mut list| list.root.take()));
+                        if let Some(link_all) = link_all {
+                            link_all.children.drain_unique(|child| thunks.push(child.list));
+                        }
+                    } else {
+                        break;
+                    }
+                }
+            }
+        }
+    };
+}
+
+macro_rules! make_catenable_iter {
+    () => {
+        /// An iterator that yields shared references to the elements of a list.
+        #[must_use = "iterators are lazy and do nothing unless consumed"]
+        pub struct Iter<'a, T> {
+            next: Option<&'a Node<T>>,
+            // The children of each node on the path to `next` that have not been visited yet.
+            children: alloc::vec::Vec<QueueIter<'a, Child<T>>>,
+            len: usize,
+        }
+
+        impl<'a, T> Iterator for Iter<'a, T> {
+            type Item = &'a T;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                let node = match self.next.take() {
+                    Some(node) => node,
+                    None => loop {
+                        let children = self.children.last_mut()?;
+                        match children.next() {
+                            Some(child) => break child.list.force(LinkAll::run).root.as_deref().expect("child lists are not empty"),
+                            None => { self.children.pop(); }
+                        }
+                    },
+                };
+
+                if !node.children.is_empty() {
+                    self.children.push(node.children.iter());
+                }
+                self.len -= 1;
+                Some(&node.elem)
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (self.len, Some(self.len))
+            }
+        }
+
+        impl<T> core::iter::FusedIterator for Iter<'_, T> {}
+
+        impl<T> ExactSizeIterator for Iter<'_, T> {}
+    };
+}
+
+macro_rules! catenable_tests {
+    () => {
+        #[cfg(test)]
+        mod tests {
+            use super::List;
+            use alloc::vec::Vec;
+
+            fn to_vec(list: &List<i32>) -> Vec<i32> {
+                list.iter().copied().collect()
+            }
+
+            #[test]
+            fn prepend_push_back() {
+                let list = List::new().push_back(2).prepend(1).push_back(3);
+                assert_eq!(list.len(), 3);
+                assert_eq!(to_vec(&list), [1, 2, 3]);
+            }
+
+            #[test]
+            fn append() {
+                let a: List<i32> = (0..5).collect();
+                let b: List<i32> = (5..10).collect();
+                let ab = a.append(&b);
+                let ba = b.append(&a);
+
+                assert_eq!(to_vec(&ab), (0..10).collect::<Vec<_>>());
+                assert_eq!(to_vec(&ba), [5, 6, 7, 8, 9, 0, 1, 2, 3, 4]);
+                assert_eq!(to_vec(&a), [0, 1, 2, 3, 4]);
+                assert_eq!(to_vec(&ab.append(&List::new())), to_vec(&ab));
+                assert_eq!(to_vec(&List::new().append(&ab)), to_vec(&ab));
+            }
+
+            #[test]
+            fn uncons() {
+                let list = List::new()
+                    .append(&(0..3).collect())
+                    .append(&(3..6).collect())
+                    .prepend(-1);
+
+                let mut rest = list;
+                let mut seen = Vec::new();
+                while let Some((&head, tail)) = rest.uncons() {
+                    seen.push(head);
+                    assert_eq!(tail.len(), rest.len() - 1);
+                    rest = tail;
+                }
+                assert_eq!(seen, [-1, 0, 1, 2, 3, 4, 5]);
+                assert!(rest.is_empty());
+                assert!(rest.tail().is_empty());
+            }
+
+            #[test]
+            fn iter_matches_uncons() {
+                let mut list = List::new();
+                for i in 0..50 {
+                    list = match i % 3 {
+                        0 => list.push_back(i),
+                        1 => list.prepend(i),
+                        _ => list.append(&list.clone()),
+                    };
+                    if list.len() > 200 {
+                        list = list.tail().tail();
+                    }
+                }
+
+                let mut expected = Vec::new();
+                let mut rest = list.clone();
+                while let Some((&head, tail)) = rest.uncons() {
+                    expected.push(head);
+                    rest = tail;
+                }
+
+                assert_eq!(list.iter().len(), expected.len());
+                assert_eq!(to_vec(&list), expected);
+            }
+
+            #[test]
+            fn shared_tail() {
+                let list = (0..1000).fold(List::new(), |list, i| list.append(&List::new().push_back(i)));
+
+                // The children of the root are only linked together once,
+                // and every call shares the result.
+                let tail = list.tail();
+                for _ in 0..1000 {
+                    let again = list.tail();
+                    assert_eq!(again.len(), 999);
+                    assert!(core::ptr::eq(again.root.as_deref().unwrap(), tail.root.as_deref().unwrap()));
+                }
+                assert!(tail.iter().copied().eq(1..1000));
+
+                let mut rest = list;
+                for i in 0..1000 {
+                    assert_eq!(rest.head(), Some(&i));
+                    assert_eq!(rest.tail().head(), rest.uncons().unwrap().1.head());
+                    rest = rest.tail();
+                }
+                assert!(rest.is_empty());
+            }
+
+            #[test]
+            fn long_drop() {
+                let mut list = List::new();
+                for i in 0..100_000 {
+                    list = list.prepend(i);
+                }
+                drop(list);
+
+                let mut list = List::new();
+                for i in 0..100_000 {
+                    list = list.push_back(i);
+                }
+                let tail = list.tail().tail();
+                drop(list);
+                drop(tail);
+            }
+        }
+    };
+}
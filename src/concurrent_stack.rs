@@ -0,0 +1,374 @@
+//! A lock-free stack that can be pushed to and popped from by many threads at once.
+//!
+//! [`Stack`] is a Treiber stack: its head is an atomic pointer, and both
+//! [`push`](Stack::push) and [`pop`](Stack::pop) work by compare-and-swapping it.
+//!
+//! Popped nodes are freed once no other thread is in the middle of a `pop`,
+//! because another thread could still be reading them. Until then, they are kept in
+//! a list of nodes waiting to be freed, which is freed by the last thread to leave `pop`.
+#![allow(unsafe_code)]
+
+use alloc::boxed::Box;
+use core::fmt;
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::mem::ManuallyDrop;
+use core::ptr;
+use core::sync::atomic::{AtomicPtr, AtomicUsize, Ordering::{Acquire, Relaxed, Release, SeqCst}};
+
+version!{0, 1, 0;
+    thread_safe: true,
+    persistent: false,
+    complexity: {
+        push: "O(1), lock-free",
+        pop: "O(1), lock-free",
+    },
+}
+
+struct Node<T> {
+    // Only the thread that pops the node takes this,
+    // so it is never dropped along with the node.
+    elem: ManuallyDrop<T>,
+    // This is atomic because it is reused to link the node into `to_be_freed`,
+    // while other threads may still be reading it in `pop`.
+    next: AtomicPtr<Node<T>>,
+}
+
+/// A lock-free stack.
+/// See the [module-level documentation](self) for more.
+pub struct Stack<T> {
+    head: AtomicPtr<Node<T>>,
+    // The number of threads currently in `pop`.
+    threads_in_pop: AtomicUsize,
+    // Popped nodes that could not be freed yet. Their elements have already been taken.
+    to_be_freed: AtomicPtr<Node<T>>,
+    _marker: PhantomData<Box<Node<T>>>,
+}
+
+// SAFETY: Elements are moved into the stack by one thread, and out of it by another,
+// but the stack never gives out references to them.
+unsafe impl<T: Send> Send for Stack<T> {}
+// SAFETY: See above.
+unsafe impl<T: Send> Sync for Stack<T> {}
+
+impl<T> Stack<T> {
+    /// Creates a new stack.
+    pub const fn new() -> Self {
+        Stack {
+            head: AtomicPtr::new(ptr::null_mut()),
+            threads_in_pop: AtomicUsize::new(0),
+            to_be_freed: AtomicPtr::new(ptr::null_mut()),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns whether the stack is empty.
+    ///
+    /// Other threads can push or pop at any time, so this may be out of date as soon as it returns.
+    pub fn is_empty(&self) -> bool {
+        self.head.load(Acquire).is_null()
+    }
+
+    /// Pushes an element onto the top of the stack.
+    pub fn push(&self, elem: T) {
+        let mut current = self.head.load(Relaxed);
+        let node = Box::into_raw(Box::new(Node {
+            elem: ManuallyDrop::new(elem),
+            next: AtomicPtr::new(current),
+        }));
+
+        // SAFETY: `node` is not shared until the exchange succeeds.
+        let next = unsafe { &(*node).next };
+        while let Err(actual) = self.head.compare_exchange_weak(current, node, SeqCst, Relaxed) {
+            current = actual;
+            next.store(current, Relaxed);
+        }
+    }
+
+    /// Pops an element off the top of the stack.
+    pub fn pop(&self) -> Option<T> {
+        // Entering `pop` and removing nodes from `head` are both `SeqCst`, so a thread that sees
+        // no other threads in `pop` after removing a node knows no other thread could have loaded it.
+        self.threads_in_pop.fetch_add(1, SeqCst);
+
+        let mut node = self.head.load(SeqCst);
+        while !node.is_null() {
+            // SAFETY: `node` was in the stack after this thread entered `pop`,
+            // so it cannot be freed until this thread leaves.
+            let next = unsafe { (*node).next.load(Relaxed) };
+            match self.head.compare_exchange_weak(node, next, SeqCst, SeqCst) {
+                Ok(_) => break,
+                Err(actual) => node = actual,
+            }
+        }
+
+        if node.is_null() {
+            self.threads_in_pop.fetch_sub(1, SeqCst);
+            return None;
+        }
+
+        // SAFETY: This thread removed `node` from the stack, so nothing else takes its element.
+        let elem = unsafe { ManuallyDrop::take(&mut (*node).elem) };
+        // SAFETY: `node` is no longer in the stack.
+        unsafe { self.free_or_defer(node) };
+        Some(elem)
+    }
+
+    /// Frees `node` if no other thread is in `pop`, or adds it to `to_be_freed` otherwise.
+    /// This also leaves `pop`.
+    ///
+    /// # Safety
+    ///
+    /// `node` must have been removed from the stack by this thread, and its element must have been taken.
+    unsafe fn free_or_defer(&self, node: *mut Node<T>) {
+        if self.threads_in_pop.load(SeqCst) == 1 {
+            // No other thread can read `node` or the nodes currently in `to_be_freed`,
+            // but another thread may enter `pop` and start adding to it at any time.
+            let pending = self.to_be_freed.swap(ptr::null_mut(), SeqCst);
+            if self.threads_in_pop.fetch_sub(1, SeqCst) == 1 {
+                // SAFETY: No threads were in `pop` when `pending` was taken.
+                unsafe { free_chain(pending) };
+            } else if !pending.is_null() {
+                let mut last = pending;
+                // SAFETY: `pending` was taken from `to_be_freed`, so this thread owns the chain,
+                // but threads that entered `pop` after it was taken could still be reading some of its nodes.
+                unsafe {
+                    loop {
+                        let next = (*last).next.load(Relaxed);
+                        if next.is_null() { break }
+                        last = next;
+                    }
+                    self.defer_chain(pending, last);
+                }
+            }
+            // SAFETY: Only this thread could see `node` when it checked `threads_in_pop`.
+            drop(unsafe { Box::from_raw(node) });
+        } else {
+            // SAFETY: The caller guarantees `node` is no longer in the stack.
+            unsafe { self.defer_chain(node, node) };
+            self.threads_in_pop.fetch_sub(1, SeqCst);
+        }
+    }
+
+    /// Adds the chain of nodes from `first` to `last`, linked through `next`, to `to_be_freed`.
+    ///
+    /// # Safety
+    ///
+    /// The nodes must not be in the stack or in `to_be_freed`, and their elements must have been taken.
+    unsafe fn defer_chain(&self, first: *mut Node<T>, last: *mut Node<T>) {
+        let mut current = self.to_be_freed.load(Relaxed);
+        loop {
+            // SAFETY: `last` is valid until it is freed by a later `pop`.
+            unsafe { (*last).next.store(current, Relaxed) };
+            match self.to_be_freed.compare_exchange_weak(current, first, Release, Relaxed) {
+                Ok(_) => break,
+                Err(actual) => current = actual,
+            }
+        }
+    }
+}
+
+/// Frees a chain of nodes linked through `next`, without dropping their elements.
+///
+/// # Safety
+///
+/// No other thread can access any of the nodes, and their elements must have been taken.
+unsafe fn free_chain<T>(mut node: *mut Node<T>) {
+    while !node.is_null() {
+        // SAFETY: The caller guarantees this is the only access to the node.
+        let boxed = unsafe { Box::from_raw(node) };
+        node = boxed.next.load(Relaxed);
+    }
+}
+
+impl<T> Default for Stack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for Stack<T> {
+    /// Other threads can change the stack at any time, so this does not show its elements.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Stack").finish_non_exhaustive()
+    }
+}
+
+impl<T> Extend<T> for Stack<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for elem in iter {
+            self.push(elem);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for Stack<T> {
+    /// Creates a stack by pushing each element of the iterator, so the last element ends up on top.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut stack = Stack::new();
+        stack.extend(iter);
+        stack
+    }
+}
+
+impl<T> IntoIterator for Stack<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { stack: self }
+    }
+}
+
+impl<T> Drop for Stack<T> {
+    fn drop(&mut self) {
+        while self.pop().is_some() {}
+        // SAFETY: No other thread can access the stack anymore.
+        unsafe { free_chain(*self.to_be_freed.get_mut()) };
+    }
+}
+
+/// An iterator that pops every element off of a stack.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct IntoIter<T> {
+    stack: Stack<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.stack.pop()
+    }
+}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+#[allow(dead_code)]
+fn assert_properties() {
+    fn is_thread_safe<T: Send + Sync>() {}
+
+    is_thread_safe::<Stack<i32>>();
+    is_thread_safe::<Stack<core::cell::Cell<i32>>>();
+    is_thread_safe::<IntoIter<i32>>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Stack;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn push_pop() {
+        let stack = Stack::new();
+        assert!(stack.is_empty());
+        assert_eq!(stack.pop(), None);
+
+        stack.push(1);
+        stack.push(2);
+        assert!(!stack.is_empty());
+        assert_eq!(stack.pop(), Some(2));
+
+        stack.push(3);
+        assert_eq!(stack.pop(), Some(3));
+        assert_eq!(stack.pop(), Some(1));
+        assert_eq!(stack.pop(), None);
+    }
+
+    #[test]
+    fn into_iter() {
+        let stack: Stack<i32> = (0..5).collect();
+        assert_eq!(stack.into_iter().collect::<Vec<_>>(), [4, 3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn drops_elements() {
+        use alloc::rc::Rc;
+
+        let rc = Rc::new(());
+        let stack: Stack<_> = (0..10).map(|_| Rc::clone(&rc)).collect();
+        drop(stack.pop());
+        assert_eq!(Rc::strong_count(&rc), 10);
+        drop(stack);
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod stress_tests {
+    use super::Stack;
+    use alloc::vec::Vec;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Barrier;
+    use std::thread;
+
+    const THREADS: usize = 8;
+    const PER_THREAD: usize = 10_000;
+
+    #[test]
+    fn every_element_popped_once() {
+        let stack = Stack::new();
+        let barrier = Barrier::new(2 * THREADS);
+
+        let mut popped: Vec<usize> = thread::scope(|s| {
+            for t in 0..THREADS {
+                let (stack, barrier) = (&stack, &barrier);
+                s.spawn(move || {
+                    barrier.wait();
+                    for i in 0..PER_THREAD {
+                        stack.push(t * PER_THREAD + i);
+                    }
+                });
+            }
+
+            let poppers: Vec<_> = (0..THREADS).map(|_| {
+                let (stack, barrier) = (&stack, &barrier);
+                s.spawn(move || {
+                    barrier.wait();
+                    let mut popped = Vec::new();
+                    while popped.len() < PER_THREAD {
+                        if let Some(elem) = stack.pop() {
+                            popped.push(elem);
+                        }
+                    }
+                    popped
+                })
+            }).collect();
+
+            poppers.into_iter().flat_map(|popper| popper.join().unwrap()).collect()
+        });
+
+        assert!(stack.is_empty());
+        popped.sort_unstable();
+        assert_eq!(popped, (0..THREADS * PER_THREAD).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn no_leaks_or_double_drops() {
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+        struct Counted;
+        impl Drop for Counted {
+            fn drop(&mut self) {
+                DROPS.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let stack = Stack::new();
+        thread::scope(|s| {
+            for _ in 0..THREADS {
+                s.spawn(|| {
+                    for i in 0..PER_THREAD {
+                        stack.push(Counted);
+                        if i % 3 != 0 {
+                            drop(stack.pop());
+                        }
+                    }
+                });
+            }
+        });
+
+        drop(stack);
+        assert_eq!(DROPS.load(Ordering::Relaxed), THREADS * PER_THREAD);
+    }
+}
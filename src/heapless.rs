@@ -0,0 +1,540 @@
+//! A singly linked list with a fixed capacity that never allocates.
+//!
+//! [`StaticList`] stores its nodes in an inline array, linked by their indices,
+//! so it can be used without [`alloc`](https://doc.rust-lang.org/alloc/).
+//! Slots that are not in use are kept in a free list, and reused by later pushes and inserts.
+use core::iter::FusedIterator;
+use core::fmt;
+
+version!{0, 1, 0;
+    thread_safe: true,
+    persistent: false,
+    complexity: {
+        push: "O(1)",
+        pop: "O(1)",
+        insert: "O(n)",
+        remove: "O(n)",
+        iter_mut: "O(N)",
+        len: "O(1)",
+    },
+}
+
+/// A singly linked list with a capacity of `N` elements, stored inline.
+/// See the [module-level documentation](self) for more.
+pub struct StaticList<T, const N: usize> {
+    nodes: [Node<T>; N],
+    head: Option<usize>,
+    // The first slot in the free list.
+    free: Option<usize>,
+    // Slots at or after this index have never been used, and are not in the free list.
+    unused: usize,
+    len: usize,
+}
+
+struct Node<T> {
+    // This is `None` when the slot is free.
+    elem: Option<T>,
+    // The next node in the list, or the next slot in the free list.
+    next: Option<usize>,
+}
+
+impl<T, const N: usize> StaticList<T, N> {
+    /// Creates a new list.
+    pub const fn new() -> Self {
+        StaticList {
+            nodes: [const { Node { elem: None, next: None } }; N],
+            head: None,
+            free: None,
+            unused: 0,
+            len: 0,
+        }
+    }
+
+    /// Pushes an element onto the front of the list.
+    ///
+    /// # Errors
+    ///
+    /// If the list is full, this returns `elem`.
+    pub fn push(&mut self, elem: T) -> Result<(), T> {
+        let index = self.alloc(elem, self.head)?;
+        self.head = Some(index);
+        Ok(())
+    }
+
+    /// Removes the first element from the list.
+    pub fn pop(&mut self) -> Option<T> {
+        let index = self.head?;
+        self.head = self.nodes[index].next;
+        Some(self.dealloc(index))
+    }
+
+    /// Inserts an element at position `index` in the list,
+    /// moving the elements after it back.
+    ///
+    /// # Errors
+    ///
+    /// If the list is full, this returns `elem`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, elem: T) -> Result<(), T> {
+        assert!(
+            index <= self.len,
+            "insertion index (is {index}) should be <= len (is {})",
+            self.len,
+        );
+
+        match index.checked_sub(1).map(|prev| self.index_of(prev)) {
+            None => self.push(elem),
+            Some(prev) => {
+                let new = self.alloc(elem, self.nodes[prev].next)?;
+                self.nodes[prev].next = Some(new);
+                Ok(())
+            }
+        }
+    }
+
+    /// Removes the element at position `index` in the list, and returns it.
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        if index >= self.len {
+            return None;
+        }
+
+        match index.checked_sub(1).map(|prev| self.index_of(prev)) {
+            None => self.pop(),
+            Some(prev) => {
+                let removed = self.nodes[prev].next?;
+                self.nodes[prev].next = self.nodes[removed].next;
+                Some(self.dealloc(removed))
+            }
+        }
+    }
+
+    /// Returns whether the list is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    /// Returns whether the list is full.
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Returns the length of the list.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the maximum number of elements the list can hold.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Removes all elements from the list.
+    pub fn clear(&mut self) {
+        while self.pop().is_some() {}
+    }
+
+    /// Returns a shared reference to the first element in the list.
+    pub fn peek(&self) -> Option<&T> {
+        self.head.and_then(|index| self.nodes[index].elem.as_ref())
+    }
+
+    /// Returns a mutable reference to the first element in the list.
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.head.and_then(|index| self.nodes[index].elem.as_mut())
+    }
+
+    /// Creates an iterator over shared references to each element in the list.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { nodes: &self.nodes, next: self.head, len: self.len }
+    }
+
+    /// Creates an iterator over mutable references to each element in the list.
+    ///
+    /// Creating the iterator takes `O(N)` time and stack space.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T, N> {
+        // The position of each slot in the list.
+        let mut positions = [0; N];
+        let mut next = self.head;
+        let mut position = 0;
+        while let Some(index) = next {
+            positions[index] = position;
+            position += 1;
+            next = self.nodes[index].next;
+        }
+
+        let mut elems = [const { None }; N];
+        for (node, &position) in self.nodes.iter_mut().zip(&positions) {
+            if let Some(elem) = &mut node.elem {
+                elems[position] = Some(elem);
+            }
+        }
+
+        IterMut { elems: elems.into_iter(), len: self.len }
+    }
+
+    /// Moves `elem` into a free slot whose next node is `next`, and returns its index.
+    fn alloc(&mut self, elem: T, next: Option<usize>) -> Result<usize, T> {
+        let index = match self.free {
+            Some(index) => {
+                self.free = self.nodes[index].next;
+                index
+            }
+            None if self.unused < N => {
+                self.unused += 1;
+                self.unused - 1
+            }
+            None => return Err(elem),
+        };
+
+        self.nodes[index] = Node { elem: Some(elem), next };
+        self.len += 1;
+        Ok(index)
+    }
+
+    /// Moves the slot at `index`, which must not be linked into the list, to the free list.
+    fn dealloc(&mut self, index: usize) -> T {
+        let node = &mut self.nodes[index];
+        node.next = self.free;
+        self.free = Some(index);
+        self.len -= 1;
+        node.elem.take().expect("slot is in use")
+    }
+
+    /// Returns the slot index of the node at position `position` in the list.
+    fn index_of(&self, position: usize) -> usize {
+        let mut index = self.head.expect("position is in bounds");
+        for _ in 0..position {
+            index = self.nodes[index].next.expect("position is in bounds");
+        }
+        index
+    }
+}
+
+impl<T, const N: usize> Extend<T> for StaticList<T, N> {
+    /// Pushes each element of the iterator onto the front of the list.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the list becomes full.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for elem in iter {
+            assert!(self.push(elem).is_ok(), "list is full (capacity is {N})");
+        }
+    }
+}
+
+impl<'a, T: 'a + Copy, const N: usize> Extend<&'a T> for StaticList<T, N> {
+    /// Pushes each element of the iterator onto the front of the list.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the list becomes full.
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<T, const N: usize> FromIterator<T> for StaticList<T, N> {
+    /// Creates a list by pushing each element of the iterator onto the front of it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the iterator has more than `N` elements.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = StaticList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T, const N: usize> IntoIterator for StaticList<T, N> {
+    type Item = T;
+    type IntoIter = IntoIter<T, N>;
+
+    fn into_iter(self) -> IntoIter<T, N> {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a StaticList<T, N> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut StaticList<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T, N>;
+
+    fn into_iter(self) -> IterMut<'a, T, N> {
+        self.iter_mut()
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for StaticList<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T, const N: usize> Default for StaticList<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An iterator that yields shared references to the elements of a list.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Iter<'a, T> {
+    nodes: &'a [Node<T>],
+    next: Option<usize>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = &self.nodes[self.next?];
+        self.next = node.next;
+        self.len -= 1;
+        node.elem.as_ref()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+/// An iterator that yields mutable references to the elements of a list.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct IterMut<'a, T, const N: usize> {
+    // The elements in list order, followed by `None`s.
+    elems: core::array::IntoIter<Option<&'a mut T>, N>,
+    len: usize,
+}
+
+impl<'a, T, const N: usize> Iterator for IterMut<'a, T, N> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let elem = self.elems.next().flatten()?;
+        self.len -= 1;
+        Some(elem)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T, const N: usize> FusedIterator for IterMut<'_, T, N> {}
+
+impl<T, const N: usize> ExactSizeIterator for IterMut<'_, T, N> {}
+
+/// An iterator that consumes a list and yields its elements.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct IntoIter<T, const N: usize> {
+    list: StaticList<T, N>,
+}
+
+impl<T, const N: usize> Iterator for IntoIter<T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len, Some(self.list.len))
+    }
+}
+
+impl<T, const N: usize> FusedIterator for IntoIter<T, N> {}
+
+impl<T, const N: usize> ExactSizeIterator for IntoIter<T, N> {}
+
+#[allow(dead_code)]
+fn assert_properties() {
+    fn list_covariant<'a, T>(x: StaticList<&'static T, 4>) -> StaticList<&'a T, 4> { x }
+    fn iter_covariant<'i, 'a, T>(x: Iter<'i, &'static T>) -> Iter<'i, &'a T> { x }
+
+    fn is_thread_safe<T: Send + Sync>() {}
+
+    is_thread_safe::<StaticList<i32, 4>>();
+    is_thread_safe::<Iter<i32>>();
+    is_thread_safe::<IterMut<i32, 4>>();
+    is_thread_safe::<IntoIter<i32, 4>>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StaticList;
+    use crate::model;
+    use alloc::vec::Vec;
+
+    fn to_vec<const N: usize>(list: &StaticList<i32, N>) -> Vec<i32> {
+        list.iter().copied().collect()
+    }
+
+    #[test]
+    fn push_pop() {
+        let mut list = StaticList::<i32, 2>::new();
+        assert_eq!(list.pop(), None);
+
+        assert_eq!(list.push(1), Ok(()));
+        assert_eq!(list.push(2), Ok(()));
+        assert!(list.is_full());
+        assert_eq!(list.push(3), Err(3));
+
+        assert_eq!(list.pop(), Some(2));
+        assert_eq!(list.push(4), Ok(()));
+        assert_eq!(list.pop(), Some(4));
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(list.pop(), None);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn peek() {
+        let mut list = StaticList::<i32, 4>::new();
+        assert_eq!(list.peek(), None);
+        assert_eq!(list.peek_mut(), None);
+
+        list.extend([1, 2]);
+        assert_eq!(list.peek(), Some(&2));
+        if let Some(elem) = list.peek_mut() {
+            *elem = 42;
+        }
+        assert_eq!(list.pop(), Some(42));
+    }
+
+    #[test]
+    fn insert_remove() {
+        let mut list = StaticList::<i32, 5>::new();
+        list.extend([3, 1]);
+        assert_eq!(list.insert(1, 2), Ok(()));
+        assert_eq!(list.insert(3, 4), Ok(()));
+        assert_eq!(list.insert(0, 0), Ok(()));
+        assert_eq!(to_vec(&list), [0, 1, 2, 3, 4]);
+        assert_eq!(list.insert(0, 9), Err(9));
+
+        assert_eq!(list.remove(5), None);
+        assert_eq!(list.remove(2), Some(2));
+        assert_eq!(list.remove(3), Some(4));
+        assert_eq!(list.remove(0), Some(0));
+        assert_eq!(to_vec(&list), [1, 3]);
+
+        // Removed slots are reused.
+        list.extend([5, 6, 7]);
+        assert_eq!(to_vec(&list), [7, 6, 5, 1, 3]);
+        assert_eq!(list.len(), list.capacity());
+    }
+
+    #[test]
+    #[should_panic = "insertion index (is 2) should be <= len (is 1)"]
+    fn insert_out_of_bounds() {
+        let mut list = StaticList::<i32, 4>::new();
+        list.extend([1]);
+        let _ = list.insert(2, 2);
+    }
+
+    #[test]
+    #[should_panic = "list is full (capacity is 2)"]
+    fn extend_full() {
+        let _: StaticList<i32, 2> = (0..3).collect();
+    }
+
+    #[test]
+    fn iter_mut() {
+        let mut list = StaticList::<i32, 6>::new();
+        list.extend([1, 2, 3, 4]);
+        list.remove(1);
+        list.insert(2, 10).unwrap();
+
+        let iter = list.iter_mut();
+        assert_eq!(iter.len(), 4);
+        for elem in iter {
+            *elem *= 2;
+        }
+        assert_eq!(to_vec(&list), [8, 4, 20, 2]);
+    }
+
+    #[test]
+    fn into_iter() {
+        let list: StaticList<i32, 3> = [1, 2, 3].into_iter().collect();
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), [3, 2, 1]);
+    }
+
+    #[test]
+    fn const_new() {
+        static EMPTY: StaticList<i32, 8> = StaticList::new();
+        assert!(EMPTY.is_empty());
+        assert_eq!(EMPTY.capacity(), 8);
+    }
+
+    #[test]
+    fn debug_fmt() {
+        use alloc::format;
+
+        let list: StaticList<i32, 3> = [1, 2].into_iter().collect();
+        assert_eq!(format!("{list:?}"), "[2, 1]");
+    }
+
+    impl model::ModelList for StaticList<i32, 32> {
+        const CAPACITY: usize = 32;
+
+        fn push(&mut self, elem: i32) {
+            StaticList::push(self, elem).unwrap();
+        }
+
+        fn pop(&mut self) -> Option<i32> {
+            StaticList::pop(self)
+        }
+
+        fn peek(&self) -> Option<&i32> {
+            StaticList::peek(self)
+        }
+
+        fn peek_mut(&mut self) -> Option<&mut i32> {
+            StaticList::peek_mut(self)
+        }
+
+        fn clear(&mut self) {
+            StaticList::clear(self);
+        }
+
+        fn len(&self) -> usize {
+            StaticList::len(self)
+        }
+
+        fn iter(&self) -> impl Iterator<Item = &i32> {
+            StaticList::iter(self)
+        }
+
+        fn iter_mut(&mut self) -> impl Iterator<Item = &mut i32> {
+            StaticList::iter_mut(self)
+        }
+
+        fn into_vec(self) -> Vec<i32> {
+            self.into_iter().collect()
+        }
+    }
+
+    #[test]
+    fn model() {
+        model::check::<StaticList<i32, 32>>();
+    }
+}
@@ -0,0 +1,37 @@
+//! A lazily evaluated, memoized stream using `Rc`.
+//!
+//! A [`Stream`] is a singly linked list where each tail is only computed
+//! the first time it is needed, and is then shared by every clone of the stream.
+//! This makes it possible to describe infinite sequences, and only pay for the
+//! part of them that actually gets used.
+//!
+//! Because this stream uses [`Rc`], it is not thread-safe.
+//! If you need this to be thread-safe, enable the `lazy_arc`
+//! crate feature, and use the [`lazy_arc`] module instead.
+//! That module has the exact same API as this one.
+//!
+//! [`lazy_arc`]: crate::lazy_arc
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+
+version!{0, 1, 0;
+    thread_safe: false,
+    persistent: true,
+    complexity: {
+        cons: "O(1)",
+        head: "O(1)",
+        tail: "O(1) after the first call",
+    },
+}
+
+make_stream!{Rc, crate::persistent::List<T>, [], [];
+/// A lazily evaluated, memoized stream that is not thread safe.
+/// See the [module-level documentation](self) for more.
+}
+
+type Init<T> = Box<dyn FnOnce(&T) -> Stream<T>>;
+
+/// The tail of a stream, which is evaluated at most once.
+type Thunk<T> = crate::thunk_common::rc::Thunk<Stream<T>, Init<T>>;
+
+stream_tests!{}
@@ -0,0 +1,48 @@
+//! A lazily evaluated, memoized stream using `Arc`.
+//!
+//! A [`Stream`] is a singly linked list where each tail is only computed
+//! the first time it is needed, and is then shared by every clone of the stream.
+//! This makes it possible to describe infinite sequences, and only pay for the
+//! part of them that actually gets used.
+//!
+//! Because this stream uses [`Arc`], it is thread-safe,
+//! but incurs some additional overhead.
+//! If two threads need the same tail at once, one of them evaluates it
+//! while the other spins until it is done.
+//! If you do not need your stream to be thread-safe,
+//! enable the `lazy` feature, and use the [`lazy`] module instead.
+//! That module has the exact same API as this one.
+//!
+//! [`lazy`]: crate::lazy
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+
+version!{0, 1, 0;
+    thread_safe: true,
+    persistent: true,
+    complexity: {
+        cons: "O(1)",
+        head: "O(1)",
+        tail: "O(1) after the first call",
+    },
+}
+
+make_stream!{Arc, crate::persistent_arc::List<T>, [+ Send + Sync], [+ Send];
+/// A lazily evaluated, memoized stream that is thread safe.
+/// See the [module-level documentation](self) for more.
+}
+
+type Init<T> = Box<dyn FnOnce(&T) -> Stream<T> + Send>;
+
+/// The tail of a stream, which is evaluated at most once.
+type Thunk<T> = crate::thunk_common::arc::Thunk<Stream<T>, Init<T>>;
+
+#[allow(dead_code)]
+fn assert_properties() {
+    fn is_thread_safe<T: Send + Sync>() {}
+
+    is_thread_safe::<Stream<i32>>();
+    is_thread_safe::<Iter<i32>>();
+}
+
+stream_tests!{}
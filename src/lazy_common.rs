@@ -0,0 +1,355 @@
+macro_rules! make_stream {
+    ($ptr: ident, $list: path, [$($elembound: tt)*], [$($fnbound: tt)*]; $(#[$streamdoc:meta])*) => {
+        $(#[$streamdoc])*
+        pub struct Stream<T> {
+            node: Option<$ptr<Node<T>>>,
+        }
+
+        struct Node<T> {
+            elem: T,
+            tail: Thunk<T>,
+        }
+
+        impl<T> Node<T> {
+            fn tail(&self) -> &Stream<T> {
+                self.tail.force(|init| init(&self.elem))
+            }
+        }
+
+        stream_impl!{$ptr, $list, [$($elembound)*], [$($fnbound)*]}
+        make_stream_iter!{}
+    };
+}
+
+macro_rules! stream_impl {
+    ($ptr: ident, $list: path, [$($elembound: tt)*], [$($fnbound: tt)*]) => {
+        impl<T> Stream<T> {
+            /// Creates a new empty stream.
+            pub fn new() -> Self {
+                Self { node: None }
+            }
+
+            /// Returns whether the stream is empty.
+            ///
+            /// This never evaluates anything.
+            pub fn is_empty(&self) -> bool {
+                self.node.is_none()
+            }
+
+            /// Returns a reference to the first element of the stream, if it exists.
+            ///
+            /// This never evaluates anything.
+            pub fn head(&self) -> Option<&T> {
+                self.node.as_ref().map(|node| &node.elem)
+            }
+
+            /// Returns the stream with the first element removed,
+            /// evaluating it if it has not been evaluated yet.
+            pub fn tail(&self) -> Self {
+                self.node.as_ref().map_or_else(Self::new, |node| node.tail().clone())
+            }
+
+            /// Creates an iterator that yields shared references to each element in the stream.
+            ///
+            /// Each tail is evaluated only when the iterator needs the element after it,
+            /// and tails that have already been evaluated are never evaluated again.
+            pub fn iter(&self) -> Iter<'_, T> {
+                Iter { current: self.node.as_deref(), started: false }
+            }
+        }
+
+        impl<T: 'static $($elembound)*> Stream<T> {
+            /// Creates a stream from its first element and a function that computes the rest of it.
+            ///
+            /// `tail` is called at most once, the first time the rest of the stream is needed.
+            pub fn cons(elem: T, tail: impl FnOnce() -> Self + 'static $($fnbound)*) -> Self {
+                Self::with_tail(elem, move |_| tail())
+            }
+
+            fn with_tail(elem: T, tail: impl FnOnce(&T) -> Self + 'static $($fnbound)*) -> Self {
+                Self { node: Some($ptr::new(Node { elem, tail: Thunk::new(Box::new(tail)) })) }
+            }
+
+            /// Creates a stream whose elements are produced by calling `f`,
+            /// which ends the first time `f` returns `None`.
+            ///
+            /// The first element is computed immediately.
+            pub fn from_fn(mut f: impl FnMut() -> Option<T> + 'static $($fnbound)*) -> Self {
+                match f() {
+                    Some(elem) => Self::with_tail(elem, move |_| Self::from_fn(f)),
+                    None => Self::new(),
+                }
+            }
+
+            /// Creates an infinite stream of `init`, `f(init)`, `f(f(init))`, and so on.
+            pub fn iterate(init: T, mut f: impl FnMut(&T) -> T + 'static $($fnbound)*) -> Self {
+                Self::with_tail(init, move |elem| {
+                    let next = f(elem);
+                    Self::iterate(next, f)
+                })
+            }
+        }
+
+        impl<T: Clone + 'static $($elembound)*> Stream<T> {
+            /// Creates an infinite stream that repeats `elem` forever.
+            pub fn repeat(elem: T) -> Self {
+                Self::with_tail(elem, |elem| Self::repeat(elem.clone()))
+            }
+
+            /// Creates a stream of the first `n` elements of this stream.
+            pub fn take(&self, n: usize) -> Self {
+                match (n, self.node.as_ref()) {
+                    (0, _) | (_, None) => Self::new(),
+                    (_, Some(node)) => {
+                        let node = node.clone();
+                        Self::cons(node.elem.clone(), move || {
+                            if n == 1 { Self::new() } else { node.tail().take(n - 1) }
+                        })
+                    }
+                }
+            }
+
+            /// Creates a stream of only the elements of this stream that satisfy `pred`.
+            ///
+            /// Finding each element evaluates this stream up to the next element that
+            /// satisfies `pred`, so this never ends if there are no more of them in an infinite stream.
+            pub fn filter(&self, mut pred: impl FnMut(&T) -> bool + 'static $($fnbound)*) -> Self {
+                let mut current = self.node.clone();
+                while let Some(node) = current {
+                    if pred(&node.elem) {
+                        return Self::cons(node.elem.clone(), move || node.tail().filter(pred));
+                    }
+                    current = node.tail().node.clone();
+                }
+                Self::new()
+            }
+
+            /// Creates a stream of pairs of elements from this stream and `other`,
+            /// which ends when either of them does.
+            pub fn zip<U: Clone + 'static $($elembound)*>(&self, other: &Stream<U>) -> Stream<(T, U)> {
+                match (self.node.as_ref(), other.node.as_ref()) {
+                    (Some(left), Some(right)) => {
+                        let (left, right) = (left.clone(), right.clone());
+                        Stream::cons((left.elem.clone(), right.elem.clone()), move || {
+                            left.tail().zip(right.tail())
+                        })
+                    }
+                    _ => Stream::new(),
+                }
+            }
+
+            /// Evaluates the whole stream, and collects it into a list in the same order.
+            ///
+            /// This never ends for an infinite stream.
+            pub fn to_list(&self) -> $list {
+                let elems: alloc::vec::Vec<&T> = self.iter().collect();
+                let mut list = <$list>::new();
+                for elem in elems.into_iter().rev() {
+                    list = list.prepend(elem.clone());
+                }
+                list
+            }
+        }
+
+        impl<T: 'static $($elembound)*> Stream<T> {
+            /// Creates a stream of the results of calling `f` on each element of this stream.
+            pub fn map<U: 'static $($elembound)*>(
+                &self,
+                mut f: impl FnMut(&T) -> U + 'static $($fnbound)*,
+            ) -> Stream<U> {
+                match self.node.as_ref() {
+                    Some(node) => {
+                        let node = node.clone();
+                        Stream::cons(f(&node.elem), move || node.tail().map(f))
+                    }
+                    None => Stream::new(),
+                }
+            }
+        }
+
+        impl<T> Clone for Stream<T> {
+            fn clone(&self) -> Self {
+                Self { node: self.node.clone() }
+            }
+        }
+
+        impl<T: core::fmt::Debug> core::fmt::Debug for Stream<T> {
+            /// Formats the elements of the stream that have already been evaluated.
+            ///
+            /// If the rest of the stream has not been evaluated, this ends with `..`.
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                let mut list = f.debug_list();
+                let mut current = self.node.as_deref();
+                while let Some(node) = current {
+                    list.entry(&node.elem);
+                    match node.tail.get() {
+                        Some(tail) => current = tail.node.as_deref(),
+                        None => return list.finish_non_exhaustive(),
+                    }
+                }
+                list.finish()
+            }
+        }
+
+        impl<T> Default for Stream<T> {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl<'a, T> IntoIterator for &'a Stream<T> {
+            type Item = &'a T;
+            type IntoIter = Iter<'a, T>;
+
+            fn into_iter(self) -> Iter<'a, T> {
+                self.iter()
+            }
+        }
+
+        impl<T> Drop for Stream<T> {
+            fn drop(&mut self) {
+                let mut cur = self.node.take();
+                while let Some(node) = cur {
+                    if let Some(node) = $ptr::into_inner(node) {
+                        cur = node.tail.into_parts().0.and_then(|mut tail| tail.node.take());
+                    } else {
+                        break;
+                    }
+                }
+            }
+        }
+    };
+}
+
+macro_rules! make_stream_iter {
+    () => {
+        /// An iterator that yields shared references to the elements of a stream.
+        #[must_use = "iterators are lazy and do nothing unless consumed"]
+        pub struct Iter<'a, T> {
+            current: Option<&'a Node<T>>,
+            // Whether `current` has already been yielded,
+            // so its tail has to be evaluated to get the next element.
+            started: bool,
+        }
+
+        impl<'a, T> Iterator for Iter<'a, T> {
+            type Item = &'a T;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                if self.started {
+                    self.current = self.current?.tail().node.as_deref();
+                }
+                self.started = true;
+                self.current.map(|node| &node.elem)
+            }
+        }
+
+        impl<T> core::iter::FusedIterator for Iter<'_, T> {}
+    };
+}
+
+macro_rules! stream_tests {
+    () => {
+        #[cfg(test)]
+        mod tests {
+            use super::Stream;
+            use alloc::vec::Vec;
+            use alloc::vec;
+
+            fn naturals() -> Stream<u64> {
+                Stream::iterate(0, |n| n + 1)
+            }
+
+            #[test]
+            fn cons_head_tail() {
+                let stream = Stream::cons(1, || Stream::cons(2, Stream::new));
+                assert_eq!(stream.head(), Some(&1));
+
+                let stream = stream.tail();
+                assert_eq!(stream.head(), Some(&2));
+
+                let stream = stream.tail();
+                assert!(stream.is_empty());
+                assert!(stream.tail().is_empty());
+            }
+
+            #[test]
+            fn infinite() {
+                let evens = naturals().filter(|n| n % 2 == 0);
+                assert_eq!(evens.take(4).iter().copied().collect::<Vec<_>>(), vec![0, 2, 4, 6]);
+
+                let squares = naturals().map(|n| n * n);
+                assert_eq!(squares.iter().nth(9), Some(&81));
+
+                let pairs = naturals().zip(&Stream::repeat('x'));
+                assert_eq!(pairs.iter().nth(3), Some(&(3, 'x')));
+            }
+
+            #[test]
+            fn primes() {
+                fn sieve(stream: &Stream<u64>) -> Stream<u64> {
+                    let prime = *stream.head().unwrap();
+                    let rest = stream.tail().filter(move |n| n % prime != 0);
+                    Stream::cons(prime, move || sieve(&rest))
+                }
+
+                let primes = sieve(&Stream::iterate(2, |n| n + 1));
+                assert_eq!(
+                    primes.take(8).to_list().iter().copied().collect::<Vec<_>>(),
+                    vec![2, 3, 5, 7, 11, 13, 17, 19],
+                );
+            }
+
+            #[test]
+            fn evaluates_once() {
+                use core::sync::atomic::{AtomicUsize, Ordering};
+                static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+                let stream = Stream::iterate(0, |n| {
+                    CALLS.fetch_add(1, Ordering::Relaxed);
+                    n + 1
+                });
+                let other = stream.clone();
+
+                assert_eq!(stream.iter().nth(5), Some(&5));
+                assert_eq!(CALLS.load(Ordering::Relaxed), 5);
+
+                assert_eq!(other.iter().take(6).count(), 6);
+                assert_eq!(stream.tail().tail().head(), Some(&2));
+                assert_eq!(CALLS.load(Ordering::Relaxed), 5);
+            }
+
+            #[test]
+            fn from_fn() {
+                let mut count = 0;
+                let stream = Stream::from_fn(move || {
+                    count += 1;
+                    (count <= 3).then_some(count)
+                });
+                assert_eq!(stream.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+            }
+
+            #[test]
+            fn debug_fmt() {
+                use alloc::format;
+
+                let stream = naturals();
+                assert_eq!(format!("{stream:?}"), "[0, ..]");
+
+                stream.iter().nth(2);
+                assert_eq!(format!("{stream:?}"), "[0, 1, 2, ..]");
+
+                let finite = stream.take(2);
+                finite.iter().count();
+                assert_eq!(format!("{finite:?}"), "[0, 1]");
+            }
+
+            #[test]
+            fn long_drop() {
+                let stream = naturals();
+                assert_eq!(stream.iter().nth(100_000), Some(&100_000));
+                drop(stream);
+            }
+        }
+    };
+}
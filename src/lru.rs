@@ -0,0 +1,473 @@
+//! A least-recently-used cache built on a doubly linked list.
+//!
+//! The entries of an [`LruCache`] are kept in a doubly linked list ordered from
+//! most to least recently used, so that the least recently used entry can be evicted in `O(1)`.
+//! The list is stored in a single `Vec` and linked by index instead of by pointer,
+//! and a hash table maps each key to the index of its entry.
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::hash::{BuildHasher, Hash};
+use core::iter::FusedIterator;
+use core::num::NonZeroUsize;
+use core::fmt;
+use hashbrown::{DefaultHashBuilder, HashTable};
+
+version!{0, 1, 0;
+    thread_safe: false,
+    persistent: false,
+    complexity: {
+        get: "expected O(1)",
+        put: "expected O(1)",
+        remove: "expected O(1)",
+        pop_lru: "expected O(1)",
+        len: "O(1)",
+    },
+}
+
+type EvictionCallback<K, V> = Box<dyn FnMut(K, V) + Send>;
+
+struct Entry<K, V> {
+    key: K,
+    value: V,
+    /// The next more recently used entry.
+    prev: Option<usize>,
+    /// The next less recently used entry.
+    next: Option<usize>,
+}
+
+/// A fixed-capacity cache that evicts the least recently used entry when it is full.
+/// See the [module-level documentation](self) for more.
+pub struct LruCache<K, V> {
+    table: HashTable<usize>,
+    hasher: DefaultHashBuilder,
+    entries: Vec<Entry<K, V>>,
+    /// The most recently used entry.
+    head: Option<usize>,
+    /// The least recently used entry.
+    tail: Option<usize>,
+    cap: NonZeroUsize,
+    on_evict: Option<EvictionCallback<K, V>>,
+}
+
+impl<K, V> LruCache<K, V> {
+    /// Creates a new, empty cache that can hold `cap` entries.
+    pub fn new(cap: NonZeroUsize) -> Self {
+        LruCache {
+            table: HashTable::new(),
+            hasher: DefaultHashBuilder::default(),
+            entries: Vec::new(),
+            head: None,
+            tail: None,
+            cap,
+            on_evict: None,
+        }
+    }
+
+    /// Creates a new, empty cache that can hold `cap` entries,
+    /// and calls `callback` with each entry it evicts to make room for another one.
+    ///
+    /// The callback is not called for entries that are removed explicitly,
+    /// such as with [`pop_lru`](Self::pop_lru) or [`remove`](Self::remove).
+    pub fn with_eviction_callback(
+        cap: NonZeroUsize,
+        callback: impl FnMut(K, V) + Send + 'static,
+    ) -> Self {
+        LruCache {
+            on_evict: Some(Box::new(callback)),
+            ..Self::new(cap)
+        }
+    }
+
+    /// Returns the maximum number of entries the cache can hold.
+    pub fn cap(&self) -> NonZeroUsize {
+        self.cap
+    }
+
+    /// Returns the number of entries in the cache.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Removes all entries from the cache, without calling the eviction callback.
+    pub fn clear(&mut self) {
+        self.table.clear();
+        self.entries.clear();
+        self.head = None;
+        self.tail = None;
+    }
+
+    /// Returns the least recently used entry, without marking it as used.
+    pub fn peek_lru(&self) -> Option<(&K, &V)> {
+        self.tail.map(|idx| {
+            let entry = &self.entries[idx];
+            (&entry.key, &entry.value)
+        })
+    }
+
+    /// Creates an iterator over the entries of the cache,
+    /// from most to least recently used.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            entries: &self.entries,
+            next: self.head,
+            len: self.len(),
+        }
+    }
+
+    /// Removes an entry from the linked list, without freeing its slot.
+    fn unlink(&mut self, idx: usize) {
+        let Entry { prev, next, .. } = self.entries[idx];
+        match prev {
+            Some(prev) => self.entries[prev].next = next,
+            None => self.head = next,
+        }
+        match next {
+            Some(next) => self.entries[next].prev = prev,
+            None => self.tail = prev,
+        }
+    }
+
+    /// Adds an unlinked entry to the front of the linked list.
+    fn push_front(&mut self, idx: usize) {
+        self.entries[idx].prev = None;
+        self.entries[idx].next = self.head;
+        match self.head {
+            Some(head) => self.entries[head].prev = Some(idx),
+            None => self.tail = Some(idx),
+        }
+        self.head = Some(idx);
+    }
+
+    fn touch(&mut self, idx: usize) {
+        if self.head != Some(idx) {
+            self.unlink(idx);
+            self.push_front(idx);
+        }
+    }
+}
+
+impl<K: Hash + Eq, V> LruCache<K, V> {
+    /// Returns a reference to the value corresponding to the key,
+    /// and marks it as the most recently used entry.
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let idx = self.find(key)?;
+        self.touch(idx);
+        Some(&self.entries[idx].value)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key,
+    /// and marks it as the most recently used entry.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let idx = self.find(key)?;
+        self.touch(idx);
+        Some(&mut self.entries[idx].value)
+    }
+
+    /// Returns a reference to the value corresponding to the key,
+    /// without marking it as used.
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(key).map(|idx| &self.entries[idx].value)
+    }
+
+    /// Returns whether the cache contains the key, without marking it as used.
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(key).is_some()
+    }
+
+    /// Inserts a key-value pair into the cache, and marks it as the most recently used entry.
+    ///
+    /// If the cache already had this key present, the value is updated,
+    /// and the old value is returned. The key itself is not updated.
+    /// Otherwise, if the cache is full, the least recently used entry is evicted.
+    pub fn put(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(idx) = self.find(&key) {
+            self.touch(idx);
+            return Some(core::mem::replace(&mut self.entries[idx].value, value));
+        }
+
+        if self.len() == self.cap.get() {
+            self.evict_lru();
+        }
+
+        let idx = self.entries.len();
+        let hash = self.hasher.hash_one(&key);
+        self.entries.push(Entry { key, value, prev: None, next: None });
+        let (entries, hasher) = (&self.entries, &self.hasher);
+        self.table.insert_unique(hash, idx, |&i| hasher.hash_one(&entries[i].key));
+        self.push_front(idx);
+        None
+    }
+
+    /// Removes a key from the cache, returning its value if it was in the cache.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let idx = self.find(key)?;
+        Some(self.remove_at(idx).1)
+    }
+
+    /// Removes and returns the least recently used entry.
+    pub fn pop_lru(&mut self) -> Option<(K, V)> {
+        self.tail.map(|idx| self.remove_at(idx))
+    }
+
+    /// Changes the capacity of the cache.
+    ///
+    /// If the cache has more entries than the new capacity,
+    /// the least recently used entries are evicted until it doesn't.
+    pub fn resize(&mut self, cap: NonZeroUsize) {
+        self.cap = cap;
+        while self.len() > cap.get() {
+            self.evict_lru();
+        }
+    }
+
+    fn evict_lru(&mut self) {
+        if let Some((key, value)) = self.pop_lru() {
+            if let Some(on_evict) = &mut self.on_evict {
+                on_evict(key, value);
+            }
+        }
+    }
+
+    fn find<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let hash = self.hasher.hash_one(key);
+        self.table.find(hash, |&i| self.entries[i].key.borrow() == key).copied()
+    }
+
+    fn remove_at(&mut self, idx: usize) -> (K, V) {
+        self.unlink(idx);
+        let hash = self.hasher.hash_one(&self.entries[idx].key);
+        self.table.find_entry(hash, |&i| i == idx)
+            .expect("every entry is in the table")
+            .remove();
+
+        // Move the last entry into the freed slot, and fix everything pointing to it.
+        let entry = self.entries.swap_remove(idx);
+        let moved = self.entries.len();
+        if idx != moved {
+            let Entry { prev, next, .. } = self.entries[idx];
+            match prev {
+                Some(prev) => self.entries[prev].next = Some(idx),
+                None => self.head = Some(idx),
+            }
+            match next {
+                Some(next) => self.entries[next].prev = Some(idx),
+                None => self.tail = Some(idx),
+            }
+
+            let hash = self.hasher.hash_one(&self.entries[idx].key);
+            *self.table.find_mut(hash, |&i| i == moved).expect("every entry is in the table") = idx;
+        }
+
+        (entry.key, entry.value)
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for LruCache<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self).finish()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a LruCache<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+/// An iterator over the entries of an [`LruCache`], from most to least recently used.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Iter<'a, K, V> {
+    entries: &'a [Entry<K, V>],
+    next: Option<usize>,
+    len: usize,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.map(|idx| {
+            let entry = &self.entries[idx];
+            self.next = entry.next;
+            self.len -= 1;
+            (&entry.key, &entry.value)
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<K, V> FusedIterator for Iter<'_, K, V> {}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+#[allow(dead_code)]
+fn assert_properties() {
+    fn is_send<T: Send>() {}
+
+    is_send::<LruCache<i32, i32>>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LruCache;
+    use alloc::vec::Vec;
+    use alloc::vec;
+    use core::num::NonZeroUsize;
+
+    fn cap(n: usize) -> NonZeroUsize {
+        NonZeroUsize::new(n).unwrap()
+    }
+
+    fn keys(cache: &LruCache<i32, i32>) -> Vec<i32> {
+        cache.iter().map(|(&k, _)| k).collect()
+    }
+
+    #[test]
+    fn put_get() {
+        let mut cache = LruCache::new(cap(2));
+        assert_eq!(cache.put(1, 10), None);
+        assert_eq!(cache.put(2, 20), None);
+        assert_eq!(cache.put(1, 11), Some(10));
+
+        assert_eq!(cache.get(&1), Some(&11));
+        assert_eq!(cache.get(&3), None);
+        if let Some(value) = cache.get_mut(&2) {
+            *value += 1;
+        }
+        assert_eq!(cache.peek(&2), Some(&21));
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn eviction_order() {
+        let mut cache = LruCache::new(cap(3));
+        cache.put(1, 1);
+        cache.put(2, 2);
+        cache.put(3, 3);
+        assert_eq!(keys(&cache), [3, 2, 1]);
+
+        // Using 1 makes 2 the least recently used.
+        cache.get(&1);
+        assert_eq!(keys(&cache), [1, 3, 2]);
+        cache.put(4, 4);
+        assert_eq!(keys(&cache), [4, 1, 3]);
+        assert!(!cache.contains(&2));
+
+        // Peeking doesn't count as using.
+        cache.peek(&3);
+        cache.put(5, 5);
+        assert_eq!(keys(&cache), [5, 4, 1]);
+
+        // Updating does.
+        cache.put(1, 100);
+        cache.put(6, 6);
+        assert_eq!(keys(&cache), [6, 1, 5]);
+    }
+
+    #[test]
+    fn eviction_callback() {
+        use alloc::sync::Arc;
+        use spin::Mutex;
+
+        let evicted = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&evicted);
+        let mut cache = LruCache::with_eviction_callback(cap(2), move |k, v| log.lock().push((k, v)));
+
+        cache.put(1, 'a');
+        cache.put(2, 'b');
+        cache.put(3, 'c');
+        assert_eq!(cache.pop_lru(), Some((2, 'b')));
+        cache.put(4, 'd');
+        cache.put(5, 'e');
+        cache.resize(cap(1));
+
+        assert_eq!(*evicted.lock(), vec![(1, 'a'), (3, 'c'), (4, 'd')]);
+        assert_eq!(cache.peek_lru(), Some((&5, &'e')));
+    }
+
+    #[test]
+    fn remove() {
+        let mut cache = LruCache::new(cap(4));
+        for i in 0..4 {
+            cache.put(i, i * 10);
+        }
+
+        assert_eq!(cache.remove(&1), Some(10));
+        assert_eq!(cache.remove(&1), None);
+        assert_eq!(keys(&cache), [3, 2, 0]);
+
+        // The entry that filled the removed slot can still be found.
+        assert_eq!(cache.get(&3), Some(&30));
+        cache.put(7, 70);
+        cache.put(8, 80);
+        assert_eq!(keys(&cache), [8, 7, 3, 2]);
+
+        while cache.pop_lru().is_some() {}
+        assert!(cache.is_empty());
+        assert_eq!(cache.peek_lru(), None);
+    }
+
+    #[test]
+    fn matches_model() {
+        let mut cache = LruCache::new(cap(8));
+        let mut model: Vec<(i32, i32)> = Vec::new();
+        let mut state = 7u32;
+
+        for i in 0..2000 {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            let key = i32::try_from((state >> 16) % 16).unwrap();
+            let pos = model.iter().position(|&(k, _)| k == key);
+
+            if state.is_multiple_of(4) {
+                assert_eq!(cache.get(&key), pos.map(|pos| &model[pos].1));
+                if let Some(pos) = pos {
+                    let entry = model.remove(pos);
+                    model.insert(0, entry);
+                }
+            } else {
+                let old = pos.map(|pos| model.remove(pos).1);
+                assert_eq!(cache.put(key, i), old);
+                model.insert(0, (key, i));
+                model.truncate(8);
+            }
+
+            assert!(cache.iter().map(|(&k, &v)| (k, v)).eq(model.iter().copied()));
+        }
+    }
+}
@@ -0,0 +1,203 @@
+//! A test harness that checks a list against a [`VecDeque`] model.
+//!
+//! A list module opts in by implementing [`ModelList`] for its list of `i32`s in its tests,
+//! and calling [`check`] from a test. The harness then applies many random sequences of
+//! operations to both the list and the model, and asserts that everything observable matches.
+//! The front of the model is the first element of the list.
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+
+/// A list that [`check`] can test against a [`VecDeque`].
+pub(crate) trait ModelList: Default {
+    /// Whether the list supports [`ModelList::peek_mut`].
+    const PEEK_MUT: bool = true;
+    /// Whether the list supports [`ModelList::iter_mut`].
+    const ITER_MUT: bool = true;
+    /// The maximum number of elements the list can hold.
+    const CAPACITY: usize = usize::MAX;
+
+    /// Pushes an element onto the front of the list.
+    fn push(&mut self, elem: i32);
+
+    fn pop(&mut self) -> Option<i32>;
+
+    fn peek(&self) -> Option<&i32>;
+
+    /// Only called if [`ModelList::PEEK_MUT`] is `true`.
+    fn peek_mut(&mut self) -> Option<&mut i32> {
+        None
+    }
+
+    /// Pushes each element onto the front of the list, in order.
+    fn extend(&mut self, elems: &[i32]) {
+        for &elem in elems {
+            self.push(elem);
+        }
+    }
+
+    fn clear(&mut self);
+
+    fn len(&self) -> usize;
+
+    fn iter(&self) -> impl Iterator<Item = &i32>;
+
+    /// Only called if [`ModelList::ITER_MUT`] is `true`.
+    fn iter_mut(&mut self) -> impl Iterator<Item = &mut i32> {
+        core::iter::empty()
+    }
+
+    /// Consumes the list, returning its elements from first to last.
+    fn into_vec(self) -> Vec<i32>;
+}
+
+#[derive(Clone, Debug)]
+enum Op {
+    Push(i32),
+    Pop,
+    Peek,
+    PeekMut(i32),
+    Extend(Vec<i32>),
+    Clear,
+    Iter,
+    IterMut(i32),
+    Len,
+}
+
+/// The number of operation sequences [`check`] runs.
+const SEQUENCES: u64 = 64;
+/// The number of operations in each sequence.
+const OPS: usize = 200;
+
+/// Checks that `L` behaves like a [`VecDeque`] over many random sequences of operations.
+///
+/// # Panics
+///
+/// Panics with the seed and the operations applied so far if the list and the model differ.
+pub(crate) fn check<L: ModelList>() {
+    for seed in 0..SEQUENCES {
+        let mut rng = Rng(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1);
+        let mut list = L::default();
+        let mut model = VecDeque::new();
+        let mut applied = Vec::new();
+
+        for _ in 0..OPS {
+            let op = random_op::<L>(&mut rng, model.len());
+            applied.push(op.clone());
+            if let Err(what) = apply(&mut list, &mut model, op) {
+                panic!("{what} differs from the model (seed {seed}) after {applied:?}");
+            }
+        }
+
+        assert_eq!(list.into_vec(), Vec::from(model), "into_vec differs from the model (seed {seed})");
+    }
+}
+
+fn random_op<L: ModelList>(rng: &mut Rng, len: usize) -> Op {
+    let room = L::CAPACITY - len;
+    loop {
+        let op = match rng.below(9) {
+            0 | 1 if room > 0 => Op::Push(rng.elem()),
+            2 => Op::Pop,
+            3 => Op::Peek,
+            4 if L::PEEK_MUT => Op::PeekMut(rng.elem()),
+            5 => {
+                let count = rng.below(8).min(room as u64);
+                Op::Extend((0..count).map(|_| rng.elem()).collect())
+            }
+            // Clearing often would keep the lists too short to be interesting.
+            6 if rng.below(8) == 0 => Op::Clear,
+            7 => Op::Iter,
+            8 if L::ITER_MUT => Op::IterMut(rng.elem()),
+            0 | 1 | 4 | 6 | 8 => continue,
+            _ => Op::Len,
+        };
+        return op;
+    }
+}
+
+/// Applies `op` to both `list` and `model`, and returns the name of the operation if they differ.
+fn apply<L: ModelList>(list: &mut L, model: &mut VecDeque<i32>, op: Op) -> Result<(), &'static str> {
+    match op {
+        Op::Push(elem) => {
+            list.push(elem);
+            model.push_front(elem);
+        }
+        Op::Pop => {
+            if list.pop() != model.pop_front() {
+                return Err("pop");
+            }
+        }
+        Op::Peek => {
+            if list.peek() != model.front() {
+                return Err("peek");
+            }
+        }
+        Op::PeekMut(elem) => match (list.peek_mut(), model.front_mut()) {
+            (Some(list_elem), Some(model_elem)) => {
+                if list_elem != model_elem {
+                    return Err("peek_mut");
+                }
+                *list_elem = elem;
+                *model_elem = elem;
+            }
+            (None, None) => {}
+            _ => return Err("peek_mut"),
+        },
+        Op::Extend(elems) => {
+            list.extend(&elems);
+            for elem in elems {
+                model.push_front(elem);
+            }
+        }
+        Op::Clear => {
+            list.clear();
+            model.clear();
+        }
+        Op::Iter => {
+            if !list.iter().eq(model.iter()) {
+                return Err("iter");
+            }
+        }
+        Op::IterMut(add) => {
+            let mut model_iter = model.iter_mut();
+            for list_elem in list.iter_mut() {
+                let Some(model_elem) = model_iter.next() else { return Err("iter_mut") };
+                if list_elem != model_elem {
+                    return Err("iter_mut");
+                }
+                *list_elem = list_elem.wrapping_add(add);
+                *model_elem = model_elem.wrapping_add(add);
+            }
+            if model_iter.next().is_some() {
+                return Err("iter_mut");
+            }
+        }
+        Op::Len => {
+            if list.len() != model.len() {
+                return Err("len");
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A xorshift generator, so that the harness does not need any dependencies.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+
+    fn elem(&mut self) -> i32 {
+        // Small elements make repeated values likely.
+        i32::try_from(self.below(64)).unwrap() - 32
+    }
+}
@@ -0,0 +1,127 @@
+use crate::ListVersion;
+
+/// Information about a list module in this crate.
+///
+/// Each module has one of these in `module::MODULE_INFO`,
+/// and [`modules`](crate::modules) returns the ones for every enabled module.
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub struct ModuleInfo {
+    /// The name of the module, like `"stack"`.
+    pub name: &'static str,
+    /// The cargo feature that enables the module.
+    pub feature: &'static str,
+    /// The [`ListVersion`] of the module.
+    pub version: ListVersion,
+    /// Whether the module's lists are `Send` and `Sync` when their elements are.
+    pub thread_safe: bool,
+    /// Whether the module's lists are persistent,
+    /// so that changing a list makes a new version of it that shares nodes with the old one.
+    pub persistent: bool,
+    /// The time complexity of the module's main operations.
+    pub complexity: &'static [Complexity],
+}
+
+/// The time complexity of an operation, in [`ModuleInfo::complexity`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Complexity {
+    /// The name of the method, like `"push"`.
+    pub operation: &'static str,
+    /// The time the method takes, like `"O(1)"` or `"amortized O(1)"`.
+    pub time: &'static str,
+}
+
+/// Returns the last segment of a path from `module_path!`.
+// Unused when no list features are enabled.
+#[allow(dead_code)]
+pub(crate) const fn module_name(path: &'static str) -> &'static str {
+    let bytes = path.as_bytes();
+    let mut start = bytes.len();
+    while start > 0 && bytes[start - 1] != b':' {
+        start -= 1;
+    }
+
+    match core::str::from_utf8(bytes.split_at(start).1) {
+        Ok(name) => name,
+        Err(_) => panic!("module paths are valid UTF-8"),
+    }
+}
+
+// Unused when no list features are enabled.
+#[allow(unused_macros)]
+macro_rules! version {
+    (
+        $major: literal, $minor: literal, $patch: literal;
+        thread_safe: $thread_safe: literal,
+        persistent: $persistent: literal,
+        complexity: { $($operation: ident: $time: literal),* $(,)? } $(,)?
+    ) => {
+        #[doc = "The current `ListVersion` of this module. "]
+        #[doc = concat!("(", $major, ".", $minor, ".", $patch, ")")]
+        #[doc = " See [its documentation](crate::ListVersion) for more information."]
+        pub const VERSION: crate::ListVersion = crate::ListVersion {
+            major: $major,
+            minor: $minor,
+            patch: $patch
+        };
+
+        #[doc = "Information about this module. See [`ModuleInfo`](crate::ModuleInfo) for more."]
+        pub const MODULE_INFO: crate::ModuleInfo = crate::ModuleInfo {
+            name: crate::module_info::module_name(module_path!()),
+            feature: crate::module_info::module_name(module_path!()),
+            version: VERSION,
+            thread_safe: $thread_safe,
+            persistent: $persistent,
+            complexity: &[$(crate::Complexity { operation: stringify!($operation), time: $time }),*],
+        };
+    };
+}
+
+/// Declares each list module behind the feature of the same name,
+/// and lists their `MODULE_INFO`s in [`modules`](crate::modules).
+macro_rules! list_modules {
+    ($($feature: literal => $name: ident,)*) => {
+        $(
+            #[cfg(feature = $feature)]
+            pub mod $name;
+        )*
+
+        /// Returns information about every list module that is enabled.
+        pub const fn modules() -> &'static [ModuleInfo] {
+            &[$(
+                #[cfg(feature = $feature)]
+                $name::MODULE_INFO,
+            )*]
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::module_name;
+    use crate::modules;
+
+    #[test]
+    fn names() {
+        assert_eq!(module_name("linked_lists::stack"), "stack");
+        assert_eq!(module_name("linked_lists"), "linked_lists");
+
+        for (i, info) in modules().iter().enumerate() {
+            assert_eq!(info.name, info.feature);
+            assert!(modules()[..i].iter().all(|other| other.name != info.name));
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "stack", feature = "persistent_arc"))]
+    fn enabled_modules() {
+        let stack = modules().iter().find(|info| info.name == "stack").unwrap();
+        assert_eq!(stack.version, crate::stack::VERSION);
+        assert!(stack.thread_safe && !stack.persistent);
+        assert!(stack.complexity.iter().any(|c| c.operation == "push" && c.time == "O(1)"));
+
+        let persistent_arc = modules().iter().find(|info| info.name == "persistent_arc").unwrap();
+        assert_eq!(persistent_arc.version, crate::persistent_arc::VERSION);
+        assert!(persistent_arc.thread_safe && persistent_arc.persistent);
+    }
+}
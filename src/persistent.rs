@@ -8,7 +8,16 @@
 //! [`persistent_arc`]: crate::persistent_arc
 use alloc::rc::Rc;
 
-version!{1, 2, 0}
+version!{1, 3, 0;
+    thread_safe: false,
+    persistent: true,
+    complexity: {
+        prepend: "O(1)",
+        tail: "O(1)",
+        head: "O(1)",
+        len: "O(n)",
+    },
+}
 
 make_list!{Rc;
 /// A singly linked shared persistent list that is not thread safe.
@@ -4,16 +4,189 @@
 //! but incurs some additional overhead.
 //! If you do not need your list to be thread-safe, 
 //! enable the `persistent` feature, and use the [`persistent`] module instead.
-//! That module has the exact same API as this one.
+//! That module has the same API as this one, except for [`AtomicList`].
+//! 
+//! An [`AtomicList`] holds a list that many threads can load and replace at once.
 //! 
 //! [`persistent`]: crate::persistent
 use alloc::sync::Arc;
+#[cfg(feature = "persistent")]
+use alloc::collections::BTreeMap;
+#[cfg(feature = "persistent")]
+use alloc::vec::Vec;
+#[cfg(feature = "persistent")]
+use core::ptr;
+
+mod atomic;
+pub use atomic::AtomicList;
 
-version!{1, 2, 0}
+version!{1, 4, 0;
+    thread_safe: true,
+    persistent: true,
+    complexity: {
+        prepend: "O(1)",
+        tail: "O(1)",
+        head: "O(1)",
+        len: "O(n)",
+    },
+}
 
 make_list!{Arc;
 /// A singly linked shared persistent list that is thread safe.
 /// See the [module-level documentation](self) for more.
 }
 
+/// Converts a [`persistent::List`] to a list with the same elements in the same order.
+/// Elements whose nodes are shared with another list are cloned, and the rest are moved.
+///
+/// To keep nodes shared between several lists shared, use [`List::from_persistent_lists`].
+///
+/// [`persistent::List`]: crate::persistent::List
+#[cfg(feature = "persistent")]
+impl<T: Clone> From<crate::persistent::List<T>> for List<T> {
+    fn from(mut list: crate::persistent::List<T>) -> Self {
+        Self::from_front(core::iter::from_fn(|| list.pop_front()))
+    }
+}
+
+#[cfg(feature = "persistent")]
+impl<T: Clone> List<T> {
+    /// Converts each [`persistent::List`] to a list with the same elements in the same order.
+    ///
+    /// Nodes that are shared between the lists are converted once,
+    /// so the new lists share them in the same way.
+    ///
+    /// [`persistent::List`]: crate::persistent::List
+    pub fn from_persistent_lists(lists: &[crate::persistent::List<T>]) -> Vec<Self> {
+        // Each element lives in its own node, so its address identifies the node.
+        let mut converted: BTreeMap<*const T, Self> = BTreeMap::new();
+        lists.iter().map(|list| {
+            let mut unconverted = Vec::new();
+            let mut tail = Self::new();
+            for elem in list {
+                if let Some(shared) = converted.get(&ptr::from_ref(elem)) {
+                    tail = shared.clone();
+                    break;
+                }
+                unconverted.push(elem);
+            }
+
+            for elem in unconverted.into_iter().rev() {
+                tail = tail.prepend(elem.clone());
+                converted.insert(ptr::from_ref(elem), tail.clone());
+            }
+            tail
+        }).collect()
+    }
+}
+
 tests!{}
+
+#[cfg(all(test, feature = "persistent"))]
+mod conversion_tests {
+    use super::List;
+    use crate::persistent;
+    use alloc::vec::Vec;
+    use core::ptr;
+
+    #[test]
+    fn from_persistent() {
+        let list: persistent::List<i32> = (0..5).collect();
+        let shared = list.tail();
+        let converted = List::from(list);
+        assert!(converted.iter().eq(&[4, 3, 2, 1, 0]));
+        assert!(shared.iter().eq(&[3, 2, 1, 0]));
+    }
+
+    #[test]
+    fn from_persistent_lists() {
+        let base: persistent::List<i32> = (0..5).collect();
+        let lists = [base.prepend(10), base.tail().prepend(20), base.clone(), persistent::List::new()];
+        let converted = List::from_persistent_lists(&lists);
+
+        for (list, converted) in lists.iter().zip(&converted) {
+            assert!(list.iter().eq(converted));
+        }
+        let base_elems: Vec<*const i32> = converted[2].iter().map(ptr::from_ref).collect();
+        assert!(converted[0].tail().iter().map(ptr::from_ref).eq(base_elems.iter().copied()));
+        assert!(converted[1].tail().iter().map(ptr::from_ref).eq(base_elems[1..].iter().copied()));
+    }
+}
+
+#[cfg(feature = "rayon")]
+mod rayon_impls {
+    use super::List;
+    use alloc::vec::Vec;
+    use rayon::iter::{FromParallelIterator, IntoParallelIterator, ParallelExtend, ParallelIterator};
+
+    par_iter!{
+        /// A parallel iterator that yields shared references to the elements of a list.
+        ParIter<'a, T>, &'a T, Sync
+    }
+
+    impl<'a, T: Sync> IntoParallelIterator for &'a List<T> {
+        type Item = &'a T;
+        type Iter = ParIter<'a, T>;
+
+        fn into_par_iter(self) -> ParIter<'a, T> {
+            ParIter { inner: self.iter().collect::<Vec<_>>().into_par_iter() }
+        }
+    }
+
+    impl<T: Send> ParallelExtend<T> for List<T> {
+        /// Prepends each element of the iterator to the list in place, in the iterator's order,
+        /// like the list's [`FromParallelIterator`] implementation.
+        fn par_extend<I: IntoParallelIterator<Item = T>>(&mut self, par_iter: I) {
+            for elem in par_iter.into_par_iter().collect::<Vec<_>>() {
+                self.push_front(elem);
+            }
+        }
+    }
+
+    impl<T: Send> FromParallelIterator<T> for List<T> {
+        /// Creates a list by prepending each element of the iterator, in the iterator's order,
+        /// like the list's [`FromIterator`] implementation.
+        fn from_par_iter<I: IntoParallelIterator<Item = T>>(par_iter: I) -> Self {
+            par_iter.into_par_iter().collect::<Vec<_>>().into_iter().collect()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::List;
+        use alloc::vec::Vec;
+        use rayon::prelude::*;
+
+        #[test]
+        fn par_iter() {
+            let list: List<u64> = (0..10_000).collect();
+            assert_eq!(list.par_iter().sum::<u64>(), list.iter().sum::<u64>());
+            assert_eq!(
+                list.par_iter().map(|elem| elem * 2).collect::<Vec<_>>(),
+                list.iter().map(|elem| elem * 2).collect::<Vec<_>>(),
+            );
+
+            let tail = list.tail();
+            assert_eq!(tail.par_iter().collect::<Vec<_>>(), list.par_iter().skip(1).collect::<Vec<_>>());
+        }
+
+        #[test]
+        fn par_collect() {
+            let list: List<u32> = (0..1000).into_par_iter().collect();
+            assert!(list.iter().eq(&(0..1000).collect::<List<u32>>()));
+        }
+
+        #[test]
+        fn par_extend() {
+            let base: List<u32> = (0..10).collect();
+            let mut list = base.clone();
+            list.par_extend((10..1000).into_par_iter());
+
+            assert!(list.iter().eq(&(0..1000).collect::<List<u32>>()));
+            assert!(base.iter().eq(&(0..10).collect::<List<u32>>()));
+        }
+    }
+}
+
+#[cfg(feature = "rayon")]
+pub use rayon_impls::ParIter;
@@ -0,0 +1,299 @@
+#![allow(unsafe_code)]
+
+use super::{List, Node};
+use alloc::sync::Arc;
+use core::fmt;
+use core::hint;
+use core::marker::PhantomData;
+use core::ptr;
+use core::sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering::{Acquire, Relaxed, Release, SeqCst}};
+
+/// A [`List`] that can be loaded and replaced atomically by many threads at once.
+///
+/// [`load`](AtomicList::load) is lock-free, and shares all of its nodes with the stored list.
+/// Replacing the list waits until no thread is in the middle of loading the old list,
+/// and only one thread can replace the list at a time.
+pub struct AtomicList<T> {
+    // The head of the list, from `Arc::into_raw`, or null if it is empty.
+    // The `AtomicList` owns one strong count of it.
+    head: AtomicPtr<Node<T>>,
+    // The number of threads in `load` that read each parity of `epoch`.
+    readers: [AtomicUsize; 2],
+    epoch: AtomicUsize,
+    writing: AtomicBool,
+    _marker: PhantomData<List<T>>,
+}
+
+// SAFETY: An `AtomicList` only hands out `List`s, which are `Send` and `Sync` under the same bounds.
+unsafe impl<T: Send + Sync> Send for AtomicList<T> {}
+// SAFETY: See above.
+unsafe impl<T: Send + Sync> Sync for AtomicList<T> {}
+
+impl<T> List<T> {
+    fn into_raw(mut self) -> *mut Node<T> {
+        self.head.take().map_or(ptr::null_mut(), |head| Arc::into_raw(head).cast_mut())
+    }
+
+    /// # Safety
+    ///
+    /// `raw` must be null, or come from `List::into_raw`.
+    unsafe fn from_raw(raw: *mut Node<T>) -> Self {
+        // SAFETY: The caller guarantees `raw` owns a strong count.
+        List { head: (!raw.is_null()).then(|| unsafe { Arc::from_raw(raw) }) }
+    }
+}
+
+impl<T> AtomicList<T> {
+    /// Creates a new `AtomicList` that holds `list`.
+    pub fn new(list: List<T>) -> Self {
+        AtomicList {
+            head: AtomicPtr::new(list.into_raw()),
+            readers: [AtomicUsize::new(0), AtomicUsize::new(0)],
+            epoch: AtomicUsize::new(0),
+            writing: AtomicBool::new(false),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the list that is currently stored.
+    pub fn load(&self) -> List<T> {
+        let readers = &self.readers[self.epoch.load(SeqCst) & 1];
+        readers.fetch_add(1, SeqCst);
+        let raw = self.head.load(SeqCst);
+        if !raw.is_null() {
+            // SAFETY: Writers wait for `readers` to be zero before dropping the list they replaced,
+            // so `raw` is still owned by this `AtomicList` or by a writer.
+            unsafe { Arc::increment_strong_count(raw) };
+        }
+        readers.fetch_sub(1, Release);
+        // SAFETY: The strong count was incremented above.
+        unsafe { List::from_raw(raw) }
+    }
+
+    /// Stores `list`, dropping the list that was stored before.
+    pub fn store(&self, list: List<T>) {
+        drop(self.swap(list));
+    }
+
+    /// Stores `list`, and returns the list that was stored before.
+    pub fn swap(&self, list: List<T>) -> List<T> {
+        self.lock();
+        let old = self.head.swap(list.into_raw(), SeqCst);
+        self.wait_for_readers();
+        self.unlock();
+        // SAFETY: `old` was owned by this `AtomicList`.
+        unsafe { List::from_raw(old) }
+    }
+
+    /// Stores `new` if the stored list is `current`, and returns the list that was stored before.
+    ///
+    /// Lists are compared by their first node, not by their elements.
+    ///
+    /// # Errors
+    ///
+    /// If the stored list is not `current`, this returns `new` without storing it.
+    pub fn compare_and_swap(&self, current: &List<T>, new: List<T>) -> Result<List<T>, List<T>> {
+        let current = current.head.as_ref().map_or(ptr::null(), Arc::as_ptr).cast_mut();
+        let new = new.into_raw();
+
+        self.lock();
+        let result = self.head.compare_exchange(current, new, SeqCst, SeqCst);
+        if result.is_ok() {
+            self.wait_for_readers();
+        }
+        self.unlock();
+
+        match result {
+            // SAFETY: `old` was owned by this `AtomicList`.
+            Ok(old) => Ok(unsafe { List::from_raw(old) }),
+            // SAFETY: `new` was never stored.
+            Err(_) => Err(unsafe { List::from_raw(new) }),
+        }
+    }
+
+    /// Stores the result of calling `f` on the stored list, and returns the list that was stored before.
+    ///
+    /// If another thread stores a list while `f` is running, `f` is called again with the new list,
+    /// so it may be called more than once.
+    pub fn rcu(&self, mut f: impl FnMut(&List<T>) -> List<T>) -> List<T> {
+        let mut current = self.load();
+        loop {
+            match self.compare_and_swap(&current, f(&current)) {
+                Ok(old) => return old,
+                Err(_) => current = self.load(),
+            }
+        }
+    }
+
+    /// Consumes the `AtomicList`, returning the list that is stored.
+    pub fn into_inner(self) -> List<T> {
+        let raw = self.head.swap(ptr::null_mut(), Relaxed);
+        // SAFETY: `raw` was owned by this `AtomicList`.
+        unsafe { List::from_raw(raw) }
+    }
+
+    fn lock(&self) {
+        while self.writing.compare_exchange_weak(false, true, Acquire, Relaxed).is_err() {
+            hint::spin_loop();
+        }
+    }
+
+    fn unlock(&self) {
+        self.writing.store(false, Release);
+    }
+
+    /// Waits until every thread that could have loaded the old head has incremented its strong count.
+    fn wait_for_readers(&self) {
+        // New readers use the other counter, so a steady stream of them cannot keep this waiting.
+        // Readers that read `epoch` before a flip can still add to the counter that was just drained,
+        // but they will load the new head, so each counter only has to be seen at zero once.
+        for _ in 0..2 {
+            let epoch = self.epoch.load(Relaxed);
+            self.epoch.store(epoch.wrapping_add(1), SeqCst);
+            while self.readers[epoch & 1].load(SeqCst) != 0 {
+                hint::spin_loop();
+            }
+        }
+    }
+}
+
+impl<T> Default for AtomicList<T> {
+    fn default() -> Self {
+        Self::new(List::new())
+    }
+}
+
+impl<T> From<List<T>> for AtomicList<T> {
+    fn from(list: List<T>) -> Self {
+        Self::new(list)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for AtomicList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("AtomicList").field(&self.load()).finish()
+    }
+}
+
+impl<T> Drop for AtomicList<T> {
+    fn drop(&mut self) {
+        // SAFETY: The head is owned by this `AtomicList`.
+        drop(unsafe { List::from_raw(*self.head.get_mut()) });
+    }
+}
+
+#[allow(dead_code)]
+fn assert_properties() {
+    fn is_thread_safe<T: Send + Sync>() {}
+
+    is_thread_safe::<AtomicList<i32>>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AtomicList, List};
+    use alloc::vec::Vec;
+
+    fn to_vec(list: &List<i32>) -> Vec<i32> {
+        list.iter().copied().collect()
+    }
+
+    #[test]
+    fn load_store_swap() {
+        let atomic = AtomicList::default();
+        assert!(atomic.load().is_empty());
+
+        let list = List::new().prepend(1).prepend(2);
+        atomic.store(list.clone());
+        assert_eq!(to_vec(&atomic.load()), [2, 1]);
+
+        let old = atomic.swap(List::new().prepend(3));
+        assert_eq!(to_vec(&old), [2, 1]);
+        assert_eq!(to_vec(&atomic.into_inner()), [3]);
+    }
+
+    #[test]
+    fn compare_and_swap() {
+        let atomic = AtomicList::new(List::new().prepend(1));
+        let current = atomic.load();
+        let stale = current.prepend(0).tail();
+
+        let new = atomic.compare_and_swap(&List::new(), current.prepend(2)).unwrap_err();
+        assert_eq!(to_vec(&new), [2, 1]);
+
+        // `stale` has the same first node as `current`.
+        let old = atomic.compare_and_swap(&stale, new).unwrap();
+        assert_eq!(to_vec(&old), [1]);
+        assert_eq!(to_vec(&atomic.load()), [2, 1]);
+
+        assert!(atomic.compare_and_swap(&current, List::new()).is_err());
+    }
+
+    #[test]
+    fn rcu_shares_nodes() {
+        let atomic = AtomicList::new(List::new().prepend(1));
+        let before = atomic.load();
+        atomic.rcu(|list| list.prepend(2));
+
+        let after = atomic.load();
+        assert_eq!(to_vec(&after), [2, 1]);
+        assert!(core::ptr::eq(after.tail().head().unwrap(), before.head().unwrap()));
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod thread_tests {
+    use super::{AtomicList, List};
+    use alloc::vec::Vec;
+    use std::thread;
+
+    #[test]
+    fn concurrent_rcu() {
+        const THREADS: i32 = 8;
+        const PER_THREAD: i32 = 1000;
+
+        let atomic = AtomicList::default();
+        thread::scope(|s| {
+            for t in 0..THREADS {
+                let atomic = &atomic;
+                s.spawn(move || {
+                    for i in 0..PER_THREAD {
+                        atomic.rcu(|list| list.prepend(t * PER_THREAD + i));
+                    }
+                });
+            }
+        });
+
+        let mut elems: Vec<i32> = atomic.load().iter().copied().collect();
+        elems.sort_unstable();
+        assert_eq!(elems, (0..THREADS * PER_THREAD).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn readers_during_writes() {
+        let atomic = AtomicList::new(List::new().prepend(0));
+        thread::scope(|s| {
+            for _ in 0..4 {
+                s.spawn(|| {
+                    for _ in 0..2000 {
+                        // Every stored list counts down from its head to 0.
+                        let list = atomic.load();
+                        let elems: Vec<usize> = list.iter().copied().collect();
+                        assert!(elems.iter().rev().copied().eq(0..elems.len()));
+                    }
+                });
+            }
+            s.spawn(|| {
+                for _ in 0..500 {
+                    atomic.rcu(|list| list.prepend(list.len()));
+                }
+                for _ in 0..500 {
+                    atomic.store(atomic.load().tail());
+                    atomic.rcu(|list| list.prepend(list.len()));
+                }
+            });
+        });
+
+        assert_eq!(atomic.load().len(), 501);
+    }
+}
@@ -5,13 +5,14 @@ macro_rules! make_list {
             head: Option<$ptr<Node<T>>>,
         }
 
+        #[derive(Clone)]
         struct Node<T> {
             elem: T,
             next: Option<$ptr<Node<T>>>,
         }
 
         list_impl!{$ptr}
-        make_iter!{}
+        make_iter!{$ptr}
     };
 }
 
@@ -34,6 +35,10 @@ macro_rules! list_impl {
             }
 
             /// Prepends an element to the front of the list, returning the new list.
+            ///
+            /// This aborts if allocating the node fails, as does every other method that adds nodes to the list.
+            /// Persistent lists have no fallible versions of these methods, because allocating
+            /// an `Rc` or `Arc` without aborting needs `Rc::try_new` or `Arc::try_new`, which are unstable.
             pub fn prepend(&self, elem: T) -> Self {
                 Self { head: Some($ptr::new(Node {
                     elem,
@@ -57,6 +62,91 @@ macro_rules! list_impl {
             pub fn iter(&self) -> Iter<'_, T> {
                 Iter { next: self.head.as_deref() }
             }
+
+            /// Prepends an element to the front of the list in place.
+            ///
+            /// Unlike [`prepend`](Self::prepend), this reuses the list's reference to its first node,
+            /// so it does not need to clone it.
+            pub fn push_front(&mut self, elem: T) {
+                let next = self.head.take();
+                self.head = Some($ptr::new(Node { elem, next }));
+            }
+
+            /// Creates a list with the elements of `iter` in the same order.
+            fn from_front(iter: impl IntoIterator<Item = T>) -> Self {
+                let mut list = Self::new();
+                let mut link = &mut list.head;
+                for elem in iter {
+                    let node = link.insert($ptr::new(Node { elem, next: None }));
+                    let Some(node) = $ptr::get_mut(node) else { unreachable!("the node was just created") };
+                    link = &mut node.next;
+                }
+                list
+            }
+
+            /// Drops the list, passing `f` each element whose node is not shared with another list.
+            #[cfg(any(feature = "catenable", feature = "catenable_arc"))]
+            pub(crate) fn drain_unique(mut self, mut f: impl FnMut(T)) {
+                let mut cur = self.head.take();
+                while let Some(node) = cur {
+                    let Some(Node { elem, next }) = $ptr::into_inner(node) else { break };
+                    cur = next;
+                    f(elem);
+                }
+            }
+        }
+
+        impl<T: Clone> List<T> {
+            /// Removes the first element of the list and returns it, or `None` if the list is empty.
+            ///
+            /// If the first node is shared with another list, the element is cloned,
+            /// and the other list is left unchanged.
+            pub fn pop_front(&mut self) -> Option<T> {
+                let node = self.head.take()?;
+                let Node { elem, next } = $ptr::unwrap_or_clone(node);
+                self.head = next;
+                Some(elem)
+            }
+
+            /// Creates an iterator that yields mutable references to each element in the list.
+            ///
+            /// Each node that is shared with another list is cloned as the iterator reaches it,
+            /// so that the other list is left unchanged.
+            /// Once a node is cloned, every node after it is shared with the original node, and is cloned too.
+            /// Nodes that only this list uses are not cloned.
+            pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+                IterMut { next: self.head.as_mut() }
+            }
+
+            /// Retains only the elements specified by the predicate,
+            /// calling it on each element once, from front to back.
+            ///
+            /// Only the nodes in front of a removed element are cloned, if they are shared with another list,
+            /// so that the other list is left unchanged.
+            pub fn retain(&mut self, mut f: impl FnMut(&T) -> bool) {
+                let mut link = &mut self.head;
+                loop {
+                    // Find the next element to remove, without cloning any nodes yet.
+                    let mut kept = 0;
+                    let mut current = link.as_deref();
+                    while let Some(node) = current {
+                        if !f(&node.elem) {
+                            break;
+                        }
+                        kept += 1;
+                        current = node.next.as_deref();
+                    }
+                    if current.is_none() {
+                        return;
+                    }
+
+                    for _ in 0..kept {
+                        let Some(node) = link else { return };
+                        link = &mut $ptr::make_mut(node).next;
+                    }
+                    *link = link.take().and_then(|node| node.next.clone());
+                }
+            }
         }
 
         impl<T> Clone for List<T> {
@@ -97,6 +187,69 @@ macro_rules! list_impl {
             }
         }
 
+        /// Converts a [`stack::List`](crate::stack::List) to a list with the same elements in the same order,
+        /// so the top of the stack is the front of the list. The elements are moved, not cloned.
+        #[cfg(feature = "stack")]
+        impl<T, A: crate::stack::Allocator> From<crate::stack::List<T, A>> for List<T> {
+            fn from(list: crate::stack::List<T, A>) -> Self {
+                Self::from_front(list)
+            }
+        }
+
+        /// Converts a `Vec` to a list with the same elements in the same order.
+        impl<T> From<alloc::vec::Vec<T>> for List<T> {
+            fn from(elems: alloc::vec::Vec<T>) -> Self {
+                Self::from_front(elems)
+            }
+        }
+
+        /// Converts a `VecDeque` to a list with the same elements in the same order.
+        impl<T> From<alloc::collections::VecDeque<T>> for List<T> {
+            fn from(elems: alloc::collections::VecDeque<T>) -> Self {
+                Self::from_front(elems)
+            }
+        }
+
+        /// Converts a boxed slice to a list with the same elements in the same order.
+        impl<T> From<alloc::boxed::Box<[T]>> for List<T> {
+            fn from(elems: alloc::boxed::Box<[T]>) -> Self {
+                Self::from_front(elems.into_vec())
+            }
+        }
+
+        /// Converts a list to a `Vec` with the same elements in the same order.
+        /// Elements whose nodes are shared with another list are cloned, and the rest are moved.
+        impl<T: Clone> From<List<T>> for alloc::vec::Vec<T> {
+            fn from(mut list: List<T>) -> Self {
+                core::iter::from_fn(|| list.pop_front()).collect()
+            }
+        }
+
+        /// Converts a list to a `VecDeque` with the same elements in the same order.
+        /// Elements whose nodes are shared with another list are cloned, and the rest are moved.
+        impl<T: Clone> From<List<T>> for alloc::collections::VecDeque<T> {
+            fn from(mut list: List<T>) -> Self {
+                core::iter::from_fn(|| list.pop_front()).collect()
+            }
+        }
+
+        /// Converts a list to a boxed slice with the same elements in the same order.
+        /// Elements whose nodes are shared with another list are cloned, and the rest are moved.
+        impl<T: Clone> From<List<T>> for alloc::boxed::Box<[T]> {
+            fn from(list: List<T>) -> Self {
+                alloc::vec::Vec::from(list).into_boxed_slice()
+            }
+        }
+
+        impl<'a, T: Clone> IntoIterator for &'a mut List<T> {
+            type Item = &'a mut T;
+            type IntoIter = IterMut<'a, T>;
+
+            fn into_iter(self) -> IterMut<'a, T> {
+                self.iter_mut()
+            }
+        }
+
         impl<T> Drop for List<T> {
             fn drop(&mut self) {
                 let mut cur = self.head.take();
@@ -113,7 +266,7 @@ macro_rules! list_impl {
 }
 
 macro_rules! make_iter {
-    () => {
+    ($ptr: ident) => {
         /// An iterator that yields shared references to the elements of a list.
         #[must_use = "iterators are lazy and do nothing unless consumed"]
         pub struct Iter<'a, T> {
@@ -147,6 +300,26 @@ macro_rules! make_iter {
                 self.size_hint().0
             }
         }
+
+        /// An iterator that yields mutable references to the elements of a list.
+        ///
+        /// See [`List::iter_mut`] for more.
+        #[must_use = "iterators are lazy and do nothing unless consumed"]
+        pub struct IterMut<'a, T> {
+            next: Option<&'a mut $ptr<Node<T>>>
+        }
+
+        impl<'a, T: Clone> Iterator for IterMut<'a, T> {
+            type Item = &'a mut T;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                self.next.take().map(|node| {
+                    let node = $ptr::make_mut(node);
+                    self.next = node.next.as_mut();
+                    &mut node.elem
+                })
+            }
+        }
     };
 }
 
@@ -155,6 +328,8 @@ macro_rules! tests {
         #[cfg(test)]
         mod tests {
             use super::List;
+            use crate::model;
+            use alloc::vec::Vec;
             #[test]
             fn prepend_tail_head() {
                 let list = List::new().prepend(1).prepend(2);
@@ -205,6 +380,138 @@ macro_rules! tests {
                 assert_eq!(iter.next(), Some(&1));
                 assert_eq!(iter.next(), None);
             }
+
+            #[test]
+            fn push_pop_front() {
+                let mut list = List::new();
+                list.push_front(1);
+                list.push_front(2);
+                let shared = list.clone();
+                list.push_front(3);
+
+                assert_eq!(list.pop_front(), Some(3));
+                assert_eq!(list.pop_front(), Some(2));
+                assert_eq!(list.pop_front(), Some(1));
+                assert_eq!(list.pop_front(), None);
+                assert!(shared.iter().eq(&[2, 1]));
+            }
+
+            #[test]
+            fn iter_mut() {
+                let mut list: List<i32> = (0..4).collect();
+                let before: Vec<*const i32> = list.iter().map(core::ptr::from_ref).collect();
+                for elem in list.iter_mut() {
+                    *elem *= 10;
+                }
+                assert!(list.iter().eq(&[30, 20, 10, 0]));
+                // Unique nodes are changed in place.
+                assert!(list.iter().map(core::ptr::from_ref).eq(before));
+
+                let shared = list.tail().tail();
+                for elem in list.iter_mut() {
+                    *elem += 1;
+                }
+                assert!(list.iter().eq(&[31, 21, 11, 1]));
+                assert!(shared.iter().eq(&[10, 0]));
+            }
+
+            #[test]
+            fn retain() {
+                let mut list: List<i32> = (0..10).collect();
+                let shared = list.tail().tail();
+                let mut seen = Vec::new();
+                list.retain(|&elem| {
+                    seen.push(elem);
+                    elem % 3 != 0
+                });
+                assert_eq!(seen, [9, 8, 7, 6, 5, 4, 3, 2, 1, 0]);
+                assert!(list.iter().eq(&[8, 7, 5, 4, 2, 1]));
+                assert!(shared.iter().eq(&[7, 6, 5, 4, 3, 2, 1, 0]));
+
+                // Nodes behind the last removed element stay shared.
+                let mut list = shared.prepend(100);
+                list.retain(|&elem| elem != 100);
+                assert!(core::ptr::eq(list.head().unwrap(), shared.head().unwrap()));
+
+                list.retain(|_| false);
+                assert!(list.is_empty());
+            }
+
+            #[test]
+            fn conversions() {
+                use alloc::boxed::Box;
+                use alloc::collections::VecDeque;
+
+                let list = List::from(alloc::vec![1, 2, 3]);
+                assert!(list.iter().eq(&[1, 2, 3]));
+                let shared = list.tail();
+                assert_eq!(Vec::from(list), [1, 2, 3]);
+                assert!(shared.iter().eq(&[2, 3]));
+
+                let list = List::from(VecDeque::from([1, 2, 3]));
+                assert_eq!(list.head(), Some(&1));
+                assert_eq!(VecDeque::from(list), [1, 2, 3]);
+
+                let list = List::from(Box::<[i32]>::from([1, 2, 3]));
+                assert_eq!(&*Box::<[i32]>::from(list), [1, 2, 3]);
+
+                assert!(List::<i32>::from(Vec::new()).is_empty());
+            }
+
+            #[test]
+            #[cfg(feature = "stack")]
+            fn from_stack() {
+                // Not `Clone`, so the elements must be moved.
+                #[derive(Debug, PartialEq)]
+                struct Elem(i32);
+
+                let stack: crate::stack::List<Elem> = (0..4).map(Elem).collect();
+                let list = List::from(stack);
+                assert!(list.iter().eq(&[Elem(3), Elem(2), Elem(1), Elem(0)]));
+            }
+
+            impl model::ModelList for List<i32> {
+                fn push(&mut self, elem: i32) {
+                    self.push_front(elem);
+                }
+
+                fn pop(&mut self) -> Option<i32> {
+                    self.pop_front()
+                }
+
+                fn peek(&self) -> Option<&i32> {
+                    self.head()
+                }
+
+                fn peek_mut(&mut self) -> Option<&mut i32> {
+                    List::iter_mut(self).next()
+                }
+
+                fn clear(&mut self) {
+                    *self = List::new();
+                }
+
+                fn len(&self) -> usize {
+                    List::len(self)
+                }
+
+                fn iter(&self) -> impl Iterator<Item = &i32> {
+                    List::iter(self)
+                }
+
+                fn iter_mut(&mut self) -> impl Iterator<Item = &mut i32> {
+                    List::iter_mut(self)
+                }
+
+                fn into_vec(self) -> Vec<i32> {
+                    self.iter().copied().collect()
+                }
+            }
+
+            #[test]
+            fn model() {
+                model::check::<List<i32>>();
+            }
         }
     }
 }
@@ -0,0 +1,40 @@
+//! A persistent FIFO queue built on a [`persistent`] list, using `Rc`.
+//!
+//! This is a banker's queue, as described by Chris Okasaki.
+//! Elements are pushed onto a rear list, and popped off a lazily evaluated front.
+//! Whenever the rear grows longer than the front, it is scheduled to be reversed
+//! onto the end of the front once the elements before it have been popped.
+//! Each part of the front is only evaluated once, and then shared by every version
+//! of the queue that contains it, so `push_back` and `pop_front` are amortized `O(1)`
+//! even when the same version of the queue is used more than once.
+//!
+//! Because this queue uses [`Rc`], it is not thread-safe.
+//! If you need this to be thread-safe, enable the `persistent_queue_arc`
+//! crate feature, and use the [`persistent_queue_arc`] module instead.
+//! That module has the exact same API as this one.
+//!
+//! [`persistent`]: crate::persistent
+//! [`persistent_queue_arc`]: crate::persistent_queue_arc
+use alloc::rc::Rc;
+use crate::persistent::List;
+
+version!{0, 1, 0;
+    thread_safe: false,
+    persistent: true,
+    complexity: {
+        push_back: "amortized O(1)",
+        pop_front: "amortized O(1)",
+        peek: "O(1)",
+        len: "O(1)",
+    },
+}
+
+make_queue!{Rc;
+/// A persistent FIFO queue that is not thread safe.
+/// See the [module-level documentation](self) for more.
+}
+
+/// The rest of a queue's front, which is evaluated at most once.
+type Thunk<T> = crate::thunk_common::rc::Thunk<Front<T>, Rotation<T>>;
+
+queue_tests!{}
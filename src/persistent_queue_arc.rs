@@ -0,0 +1,51 @@
+//! A persistent FIFO queue built on a [`persistent_arc`] list, using `Arc`.
+//!
+//! This is a banker's queue, as described by Chris Okasaki.
+//! Elements are pushed onto a rear list, and popped off a lazily evaluated front.
+//! Whenever the rear grows longer than the front, it is scheduled to be reversed
+//! onto the end of the front once the elements before it have been popped.
+//! Each part of the front is only evaluated once, and then shared by every version
+//! of the queue that contains it, so `push_back` and `pop_front` are amortized `O(1)`
+//! even when the same version of the queue is used more than once.
+//!
+//! Because this queue uses [`Arc`], it is thread-safe,
+//! but incurs some additional overhead.
+//! If two threads need the same part of the front at once, one of them evaluates it
+//! while the other spins until it is done.
+//! If you do not need your queue to be thread-safe,
+//! enable the `persistent_queue` feature, and use the [`persistent_queue`] module instead.
+//! That module has the exact same API as this one.
+//!
+//! [`persistent_arc`]: crate::persistent_arc
+//! [`persistent_queue`]: crate::persistent_queue
+use alloc::sync::Arc;
+use crate::persistent_arc::List;
+
+version!{0, 1, 0;
+    thread_safe: true,
+    persistent: true,
+    complexity: {
+        push_back: "amortized O(1)",
+        pop_front: "amortized O(1)",
+        peek: "O(1)",
+        len: "O(1)",
+    },
+}
+
+make_queue!{Arc;
+/// A persistent FIFO queue that is thread safe.
+/// See the [module-level documentation](self) for more.
+}
+
+/// The rest of a queue's front, which is evaluated at most once.
+type Thunk<T> = crate::thunk_common::arc::Thunk<Front<T>, Rotation<T>>;
+
+#[allow(dead_code)]
+fn assert_properties() {
+    fn is_thread_safe<T: Send + Sync>() {}
+
+    is_thread_safe::<Queue<i32>>();
+    is_thread_safe::<Iter<i32>>();
+}
+
+queue_tests!{}
@@ -0,0 +1,411 @@
+macro_rules! make_queue {
+    ($ptr: ident; $(#[$queuedoc:meta])*) => {
+        $(#[$queuedoc])*
+        pub struct Queue<T> {
+            front: Front<T>,
+            front_len: usize,
+            // Newest element first.
+            rear: List<$ptr<T>>,
+            rear_len: usize,
+        }
+
+        /// A lazily evaluated stream of the elements at the front of a queue.
+        struct Front<T> {
+            node: Option<$ptr<Node<T>>>,
+        }
+
+        struct Node<T> {
+            elem: $ptr<T>,
+            next: Thunk<T>,
+        }
+
+        /// What is left of a rotation: the elements after the head of `front`,
+        /// followed by the elements of `rear` from oldest to newest.
+        struct Rotation<T> {
+            front: Front<T>,
+            rear: List<$ptr<T>>,
+        }
+
+        front_impl!{$ptr}
+        queue_impl!{$ptr}
+        make_queue_iter!{}
+    };
+}
+
+macro_rules! front_impl {
+    ($ptr: ident) => {
+        impl<T> Front<T> {
+            fn new() -> Self {
+                Self { node: None }
+            }
+
+            fn cons(elem: $ptr<T>, next: Thunk<T>) -> Self {
+                Self { node: Some($ptr::new(Node { elem, next })) }
+            }
+
+            fn head(&self) -> Option<&$ptr<T>> {
+                self.node.as_ref().map(|node| &node.elem)
+            }
+
+            /// Returns the stream with the first element removed,
+            /// evaluating it if it has not been evaluated yet.
+            fn tail(&self) -> Self {
+                self.node.as_ref().map_or_else(Self::new, |node| node.next.force(Rotation::run).clone())
+            }
+
+            /// Creates a fully evaluated stream of the elements of `rear`, oldest first.
+            fn reverse(rear: &List<$ptr<T>>) -> Self {
+                let mut front = Self::new();
+                for elem in rear {
+                    front = Self::cons(elem.clone(), Thunk::ready(front));
+                }
+                front
+            }
+
+            /// Creates a stream of the elements of `front` followed by those of `rear`, oldest first.
+            ///
+            /// Each element is only computed the first time it is needed,
+            /// except that all of `rear` is reversed at once when `front` runs out.
+            fn rotate(front: Self, rear: List<$ptr<T>>) -> Self {
+                match front.head().cloned() {
+                    Some(elem) => Self::cons(elem, Thunk::new(Rotation { front, rear })),
+                    None => Self::reverse(&rear),
+                }
+            }
+        }
+
+        impl<T> Rotation<T> {
+            /// Evaluates the next step of the rotation.
+            fn run(self) -> Front<T> {
+                Front::rotate(self.front.tail(), self.rear)
+            }
+        }
+
+        impl<T> Clone for Front<T> {
+            fn clone(&self) -> Self {
+                Self { node: self.node.clone() }
+            }
+        }
+
+        impl<T> Drop for Front<T> {
+            fn drop(&mut self) {
+                let mut cur = self.node.take();
+                while let Some(node) = cur {
+                    let Some(node) = $ptr::into_inner(node) else { break };
+                    cur = match node.next.into_parts() {
+                        (Some(mut next), _) => next.node.take(),
+                        (None, Some(mut rotation)) => rotation.front.node.take(),
+                        (None, None) => None,
+                    };
+                }
+            }
+        }
+    };
+}
+
+macro_rules! queue_impl {
+    ($ptr: ident) => {
+        impl<T> Queue<T> {
+            /// Creates a new queue.
+            pub fn new() -> Self {
+                Self {
+                    front: Front::new(),
+                    front_len: 0,
+                    rear: List::new(),
+                    rear_len: 0,
+                }
+            }
+
+            /// Returns whether the queue is empty.
+            pub fn is_empty(&self) -> bool {
+                // The rear is never longer than the front,
+                // so an empty front means an empty queue.
+                self.front.node.is_none()
+            }
+
+            /// Returns the length of the queue.
+            pub fn len(&self) -> usize {
+                self.front_len + self.rear_len
+            }
+
+            /// Returns a reference to the element at the front of the queue, if it exists.
+            pub fn peek(&self) -> Option<&T> {
+                self.front.head().map(|elem| &**elem)
+            }
+
+            /// Adds an element to the back of the queue, returning the new queue.
+            ///
+            /// This is amortized `O(1)`, even if the same version of the queue is used more than once.
+            pub fn push_back(&self, elem: T) -> Self {
+                Self {
+                    front: self.front.clone(),
+                    front_len: self.front_len,
+                    rear: self.rear.prepend($ptr::new(elem)),
+                    rear_len: self.rear_len + 1,
+                }.balance()
+            }
+
+            /// Returns the queue with the element at the front removed.
+            pub(crate) fn without_front(&self) -> Self {
+                Self {
+                    front: self.front.tail(),
+                    front_len: self.front_len.saturating_sub(1),
+                    rear: self.rear.clone(),
+                    rear_len: self.rear_len,
+                }.balance()
+            }
+
+            /// Keeps the rear list at most as long as the front,
+            /// by starting to rotate the rear onto the end of the front when it gets too long.
+            fn balance(self) -> Self {
+                if self.rear_len <= self.front_len {
+                    return self;
+                }
+
+                Self {
+                    front: Front::rotate(self.front, self.rear),
+                    front_len: self.front_len + self.rear_len,
+                    rear: List::new(),
+                    rear_len: 0,
+                }
+            }
+
+            /// Creates an iterator that yields shared references to each element in the queue,
+            /// from front to back.
+            pub fn iter(&self) -> Iter<'_, T> {
+                Iter {
+                    front: self.front.node.as_deref(),
+                    rear: self.rear.iter().map(|elem| &**elem).collect(),
+                    len: self.len(),
+                }
+            }
+
+            /// Drops the queue, passing `f` each element that is not shared with another queue.
+            #[cfg(any(feature = "catenable", feature = "catenable_arc"))]
+            pub(crate) fn drain_unique(mut self, mut f: impl FnMut(T)) {
+                let mut unwrap = |elem| {
+                    if let Some(elem) = $ptr::into_inner(elem) {
+                        f(elem);
+                    }
+                };
+
+                let mut cur = self.front.node.take();
+                while let Some(node) = cur {
+                    let Some(Node { elem, next }) = $ptr::into_inner(node) else { break };
+                    unwrap(elem);
+                    cur = match next.into_parts() {
+                        (Some(mut next), _) => next.node.take(),
+                        (None, Some(mut rotation)) => {
+                            let node = rotation.front.node.take();
+                            rotation.rear.drain_unique(&mut unwrap);
+                            node
+                        }
+                        (None, None) => None,
+                    };
+                }
+                self.rear.drain_unique(unwrap);
+            }
+        }
+
+        impl<T: Clone> Queue<T> {
+            /// Removes the element at the front of the queue,
+            /// returning it along with the new queue.
+            ///
+            /// This is amortized `O(1)`, even if the same version of the queue is used more than once.
+            pub fn pop_front(&self) -> Option<(T, Self)> {
+                let elem = T::clone(self.peek()?);
+                Some((elem, self.without_front()))
+            }
+        }
+
+        impl<T> Clone for Queue<T> {
+            fn clone(&self) -> Self {
+                Self {
+                    front: self.front.clone(),
+                    front_len: self.front_len,
+                    rear: self.rear.clone(),
+                    rear_len: self.rear_len,
+                }
+            }
+        }
+
+        impl<T: core::fmt::Debug> core::fmt::Debug for Queue<T> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_list().entries(self).finish()
+            }
+        }
+
+        impl<T> Default for Queue<T> {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl<T> FromIterator<T> for Queue<T> {
+            fn from_iter<I: IntoIterator<Item=T>>(iter: I) -> Self {
+                let mut queue = Queue::new();
+                for elem in iter {
+                    queue = queue.push_back(elem);
+                }
+                queue
+            }
+        }
+
+        impl<'a, T> IntoIterator for &'a Queue<T> {
+            type Item = &'a T;
+            type IntoIter = Iter<'a, T>;
+
+            fn into_iter(self) -> Iter<'a, T> {
+                self.iter()
+            }
+        }
+    };
+}
+
+macro_rules! make_queue_iter {
+    () => {
+        /// An iterator that yields shared references to the elements of a queue,
+        /// from front to back.
+        #[must_use = "iterators are lazy and do nothing unless consumed"]
+        pub struct Iter<'a, T> {
+            front: Option<&'a Node<T>>,
+            // Newest element first, so popping yields them in order.
+            rear: alloc::vec::Vec<&'a T>,
+            len: usize,
+        }
+
+        impl<'a, T> Iterator for Iter<'a, T> {
+            type Item = &'a T;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                let elem = match self.front {
+                    Some(node) => {
+                        self.front = node.next.force(Rotation::run).node.as_deref();
+                        &*node.elem
+                    }
+                    None => self.rear.pop()?,
+                };
+                self.len -= 1;
+                Some(elem)
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (self.len, Some(self.len))
+            }
+        }
+
+        impl<T> core::iter::FusedIterator for Iter<'_, T> {}
+
+        impl<T> ExactSizeIterator for Iter<'_, T> {}
+    };
+}
+
+macro_rules! queue_tests {
+    () => {
+        #[cfg(test)]
+        mod tests {
+            use super::Queue;
+            use alloc::vec::Vec;
+
+            #[test]
+            fn push_pop() {
+                let queue = Queue::new().push_back(1).push_back(2).push_back(3);
+                assert_eq!(queue.peek(), Some(&1));
+
+                let (elem, queue) = queue.pop_front().unwrap();
+                assert_eq!(elem, 1);
+                let queue = queue.push_back(4);
+
+                let (elem, queue) = queue.pop_front().unwrap();
+                assert_eq!(elem, 2);
+                let (elem, queue) = queue.pop_front().unwrap();
+                assert_eq!(elem, 3);
+                let (elem, queue) = queue.pop_front().unwrap();
+                assert_eq!(elem, 4);
+
+                assert!(queue.pop_front().is_none());
+                assert_eq!(queue.peek(), None);
+            }
+
+            #[test]
+            fn len() {
+                let queue: Queue<i32> = Queue::new();
+                assert_eq!(queue.len(), 0);
+                assert!(queue.is_empty());
+
+                let queue: Queue<i32> = (0..10).collect();
+                assert_eq!(queue.len(), 10);
+
+                let (_, queue) = queue.pop_front().unwrap();
+                assert_eq!(queue.len(), 9);
+                assert!(!queue.is_empty());
+            }
+
+            #[test]
+            fn persistence() {
+                let base: Queue<i32> = (0..5).collect();
+                let a = base.push_back(10);
+                let b = base.push_back(20);
+                let (_, c) = base.pop_front().unwrap();
+
+                assert_eq!(base.iter().copied().collect::<Vec<_>>(), [0, 1, 2, 3, 4]);
+                assert_eq!(a.iter().copied().collect::<Vec<_>>(), [0, 1, 2, 3, 4, 10]);
+                assert_eq!(b.iter().copied().collect::<Vec<_>>(), [0, 1, 2, 3, 4, 20]);
+                assert_eq!(c.iter().copied().collect::<Vec<_>>(), [1, 2, 3, 4]);
+            }
+
+            #[test]
+            fn iter() {
+                let mut queue = Queue::new();
+                for i in 0..20 {
+                    queue = queue.push_back(i);
+                    if i % 3 == 0 {
+                        queue = queue.pop_front().unwrap().1;
+                    }
+                }
+
+                let iter = queue.iter();
+                assert_eq!(iter.len(), queue.len());
+                assert!(iter.copied().eq(7..20));
+            }
+
+            #[test]
+            fn shared_versions() {
+                // The last push starts rotating 7..15 onto the end of the front,
+                // so after popping 0..6, the next pop has to reverse all of them.
+                let mut queue: Queue<i32> = (0..15).collect();
+                for _ in 0..6 {
+                    queue = queue.pop_front().unwrap().1;
+                }
+
+                let popped: Vec<_> = (0..100).map(|_| queue.pop_front().unwrap()).collect();
+                let first = popped[0].1.front.node.as_deref().unwrap();
+                for (elem, rest) in &popped {
+                    assert_eq!(*elem, 6);
+                    assert!(rest.iter().copied().eq(7..15));
+                    // The reversal is only done once, and its result is shared.
+                    assert!(core::ptr::eq(rest.front.node.as_deref().unwrap(), first));
+                }
+
+                // The rear is as long as the front, so popping from this starts a new rotation.
+                let full = (15..24).fold(queue, |queue, i| queue.push_back(i));
+                assert_eq!((full.front_len, full.rear_len), (9, 9));
+                for _ in 0..100 {
+                    let (elem, rest) = full.pop_front().unwrap();
+                    assert_eq!(elem, 6);
+                    assert_eq!(rest.rear_len, 0);
+                    assert!(rest.iter().copied().eq(7..24));
+                }
+                assert!(full.iter().copied().eq(6..24));
+            }
+
+            #[test]
+            fn long_drop() {
+                let queue: Queue<i32> = (0..100_000).collect();
+                let (_, rest) = queue.pop_front().unwrap();
+                drop(queue);
+                drop(rest);
+            }
+        }
+    };
+}
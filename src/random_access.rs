@@ -0,0 +1,34 @@
+//! A persistent random-access list using `Rc`.
+//!
+//! This list is a skew binary random-access list, as described by Chris Okasaki.
+//! It stores its elements in a list of complete binary trees with increasing sizes,
+//! so like [`persistent::List`](crate::persistent::List) it can `prepend`, `tail`,
+//! and `head` in `O(1)`, but it can also `get` and `set` any element in `O(log n)`.
+//!
+//! Because this list uses [`Rc`], it is not thread-safe.
+//! If you need this to be thread-safe, enable the `random_access_arc`
+//! crate feature, and use the [`random_access_arc`] module instead.
+//! That module has the exact same API as this one.
+//!
+//! [`random_access_arc`]: crate::random_access_arc
+use alloc::rc::Rc;
+
+version!{0, 1, 0;
+    thread_safe: false,
+    persistent: true,
+    complexity: {
+        prepend: "O(1)",
+        tail: "O(1)",
+        head: "O(1)",
+        get: "O(log n)",
+        set: "O(log n)",
+        len: "O(1)",
+    },
+}
+
+make_random_access_list!{Rc;
+/// A persistent random-access list that is not thread safe.
+/// See the [module-level documentation](self) for more.
+}
+
+random_access_tests!{}
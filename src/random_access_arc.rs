@@ -0,0 +1,35 @@
+//! A persistent random-access list using `Arc`.
+//!
+//! This list is a skew binary random-access list, as described by Chris Okasaki.
+//! It stores its elements in a list of complete binary trees with increasing sizes,
+//! so like [`persistent_arc::List`](crate::persistent_arc::List) it can `prepend`, `tail`,
+//! and `head` in `O(1)`, but it can also `get` and `set` any element in `O(log n)`.
+//!
+//! Because this list uses [`Arc`], it is thread-safe,
+//! but incurs some additional overhead.
+//! If you do not need your list to be thread-safe,
+//! enable the `random_access` feature, and use the [`random_access`] module instead.
+//! That module has the exact same API as this one.
+//!
+//! [`random_access`]: crate::random_access
+use alloc::sync::Arc;
+
+version!{0, 1, 0;
+    thread_safe: true,
+    persistent: true,
+    complexity: {
+        prepend: "O(1)",
+        tail: "O(1)",
+        head: "O(1)",
+        get: "O(log n)",
+        set: "O(log n)",
+        len: "O(1)",
+    },
+}
+
+make_random_access_list!{Arc;
+/// A persistent random-access list that is thread safe.
+/// See the [module-level documentation](self) for more.
+}
+
+random_access_tests!{}
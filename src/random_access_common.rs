@@ -0,0 +1,353 @@
+macro_rules! make_random_access_list {
+    ($ptr: ident; $(#[$listdoc:meta])*) => {
+        $(#[$listdoc])*
+        pub struct List<T> {
+            spine: Option<$ptr<Spine<T>>>,
+            len: usize,
+        }
+
+        /// One complete binary tree in the list, along with the trees after it.
+        struct Spine<T> {
+            size: usize,
+            tree: $ptr<Tree<T>>,
+            next: Option<$ptr<Spine<T>>>,
+        }
+
+        /// A complete binary tree, with elements stored in preorder.
+        struct Tree<T> {
+            elem: T,
+            children: Option<($ptr<Tree<T>>, $ptr<Tree<T>>)>,
+        }
+
+        random_access_list_impl!{$ptr}
+        make_random_access_iter!{}
+    };
+}
+
+macro_rules! random_access_list_impl {
+    ($ptr: ident) => {
+        impl<T> List<T> {
+            /// Creates a new list.
+            pub fn new() -> Self {
+                Self { spine: None, len: 0 }
+            }
+
+            /// Returns whether the list is empty.
+            pub fn is_empty(&self) -> bool {
+                self.spine.is_none()
+            }
+
+            /// Returns the length of the list.
+            pub fn len(&self) -> usize {
+                self.len
+            }
+
+            /// Prepends an element to the front of the list, returning the new list.
+            pub fn prepend(&self, elem: T) -> Self {
+                let spine = match self.spine.as_deref() {
+                    Some(Spine { size, tree: left, next: Some(second) }) if *size == second.size => {
+                        Spine {
+                            size: 2 * size + 1,
+                            tree: $ptr::new(Tree {
+                                elem,
+                                children: Some((left.clone(), second.tree.clone())),
+                            }),
+                            next: second.next.clone(),
+                        }
+                    }
+                    _ => Spine {
+                        size: 1,
+                        tree: $ptr::new(Tree { elem, children: None }),
+                        next: self.spine.clone(),
+                    },
+                };
+
+                Self { spine: Some($ptr::new(spine)), len: self.len + 1 }
+            }
+
+            /// Returns the list with the first element removed.
+            pub fn tail(&self) -> Self {
+                let Some(first) = self.spine.as_deref() else {
+                    return Self::new();
+                };
+
+                let spine = match &first.tree.children {
+                    None => first.next.clone(),
+                    Some((left, right)) => {
+                        let size = first.size / 2;
+                        let right = Spine { size, tree: right.clone(), next: first.next.clone() };
+                        let left = Spine { size, tree: left.clone(), next: Some($ptr::new(right)) };
+                        Some($ptr::new(left))
+                    }
+                };
+
+                Self { spine, len: self.len - 1 }
+            }
+
+            /// Returns a reference to the first element in the list, if it exists.
+            pub fn head(&self) -> Option<&T> {
+                self.spine.as_ref().map(|spine| &spine.tree.elem)
+            }
+
+            /// Returns a reference to the element at `index`, if it exists.
+            ///
+            /// This takes `O(log n)` time.
+            pub fn get(&self, mut index: usize) -> Option<&T> {
+                let mut spine = self.spine.as_deref();
+                while let Some(current) = spine {
+                    if index < current.size {
+                        return Some(Tree::get(&current.tree, current.size, index));
+                    }
+                    index -= current.size;
+                    spine = current.next.as_deref();
+                }
+                None
+            }
+
+            /// Creates an iterator that yields shared references to each element in the list.
+            pub fn iter(&self) -> Iter<'_, T> {
+                Iter {
+                    spine: self.spine.as_deref(),
+                    trees: alloc::vec::Vec::new(),
+                    len: self.len,
+                }
+            }
+        }
+
+        impl<T: Clone> List<T> {
+            /// Returns a new list with the element at `index` replaced by `elem`.
+            ///
+            /// This takes `O(log n)` time, and the new list shares
+            /// all but `O(log n)` of its nodes with `self`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `index` is out of bounds.
+            pub fn set(&self, index: usize, elem: T) -> Self {
+                assert!(
+                    index < self.len,
+                    "index out of bounds: the len is {} but the index is {index}",
+                    self.len,
+                );
+
+                Self {
+                    spine: Some(Spine::set(self.spine.as_ref().unwrap(), index, elem)),
+                    len: self.len,
+                }
+            }
+        }
+
+        impl<T: Clone> Spine<T> {
+            fn set(spine: &$ptr<Spine<T>>, index: usize, elem: T) -> $ptr<Spine<T>> {
+                if index < spine.size {
+                    $ptr::new(Spine {
+                        size: spine.size,
+                        tree: Tree::set(&spine.tree, spine.size, index, elem),
+                        next: spine.next.clone(),
+                    })
+                } else {
+                    let next = spine.next.as_ref().expect("index is in bounds");
+                    $ptr::new(Spine {
+                        size: spine.size,
+                        tree: spine.tree.clone(),
+                        next: Some(Spine::set(next, index - spine.size, elem)),
+                    })
+                }
+            }
+        }
+
+        impl<T> Tree<T> {
+            fn get(mut tree: &Tree<T>, mut size: usize, mut index: usize) -> &T {
+                while let Some((left, right)) = &tree.children {
+                    if index == 0 { break }
+                    size /= 2;
+                    if index <= size {
+                        tree = left;
+                        index -= 1;
+                    } else {
+                        tree = right;
+                        index -= size + 1;
+                    }
+                }
+                &tree.elem
+            }
+        }
+
+        impl<T: Clone> Tree<T> {
+            fn set(tree: &$ptr<Tree<T>>, size: usize, index: usize, elem: T) -> $ptr<Tree<T>> {
+                if index == 0 {
+                    return $ptr::new(Tree { elem, children: tree.children.clone() });
+                }
+
+                let (left, right) = tree.children.as_ref().expect("index is in bounds");
+                let half = size / 2;
+                let children = if index <= half {
+                    (Tree::set(left, half, index - 1, elem), right.clone())
+                } else {
+                    (left.clone(), Tree::set(right, half, index - half - 1, elem))
+                };
+
+                $ptr::new(Tree { elem: tree.elem.clone(), children: Some(children) })
+            }
+        }
+
+        impl<T> Clone for List<T> {
+            fn clone(&self) -> Self {
+                Self { spine: self.spine.clone(), len: self.len }
+            }
+        }
+
+        impl<T: core::fmt::Debug> core::fmt::Debug for List<T> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_list().entries(self).finish()
+            }
+        }
+
+        impl<T> Default for List<T> {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl<T> FromIterator<T> for List<T> {
+            fn from_iter<I: IntoIterator<Item=T>>(iter: I) -> Self {
+                let mut list = List::new();
+                for elem in iter {
+                    list = list.prepend(elem);
+                }
+                list
+            }
+        }
+
+        impl<'a, T> IntoIterator for &'a List<T> {
+            type Item = &'a T;
+            type IntoIter = Iter<'a, T>;
+
+            fn into_iter(self) -> Iter<'a, T> {
+                self.iter()
+            }
+        }
+    };
+}
+
+macro_rules! make_random_access_iter {
+    () => {
+        /// An iterator that yields shared references to the elements of a list.
+        #[must_use = "iterators are lazy and do nothing unless consumed"]
+        pub struct Iter<'a, T> {
+            spine: Option<&'a Spine<T>>,
+            // The subtrees of the current tree that have not been visited yet,
+            // with the next one on top.
+            trees: alloc::vec::Vec<&'a Tree<T>>,
+            len: usize,
+        }
+
+        impl<'a, T> Iterator for Iter<'a, T> {
+            type Item = &'a T;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                let tree = match self.trees.pop() {
+                    Some(tree) => tree,
+                    None => {
+                        let spine = self.spine?;
+                        self.spine = spine.next.as_deref();
+                        &spine.tree
+                    }
+                };
+
+                if let Some((left, right)) = &tree.children {
+                    self.trees.push(right);
+                    self.trees.push(left);
+                }
+                self.len -= 1;
+                Some(&tree.elem)
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (self.len, Some(self.len))
+            }
+        }
+
+        impl<T> core::iter::FusedIterator for Iter<'_, T> {}
+
+        impl<T> ExactSizeIterator for Iter<'_, T> {}
+    };
+}
+
+macro_rules! random_access_tests {
+    () => {
+        #[cfg(test)]
+        mod tests {
+            use super::List;
+            use alloc::vec::Vec;
+
+            #[test]
+            fn prepend_tail_head() {
+                let list = List::new().prepend(1).prepend(2);
+
+                assert_eq!(list.head(), Some(&2));
+                let list = list.tail();
+
+                assert_eq!(list.head(), Some(&1));
+                let list = list.tail();
+
+                assert_eq!(list.head(), None);
+                let list = list.tail();
+
+                assert_eq!(list.head(), None);
+                assert!(list.is_empty());
+            }
+
+            #[test]
+            fn get() {
+                let list: List<usize> = (0..100).rev().collect();
+                assert_eq!(list.len(), 100);
+
+                for i in 0..100 {
+                    assert_eq!(list.get(i), Some(&i));
+                }
+                assert_eq!(list.get(100), None);
+
+                let mut list = list;
+                for i in 0..100 {
+                    assert_eq!(list.head(), Some(&i));
+                    assert_eq!(list.get(99 - i), Some(&99));
+                    list = list.tail();
+                }
+                assert!(list.is_empty());
+            }
+
+            #[test]
+            fn set() {
+                let list: List<usize> = (0..50).rev().collect();
+                let mut updated = list.clone();
+                for i in (0..50).step_by(3) {
+                    updated = updated.set(i, i * 100);
+                }
+
+                for i in 0..50 {
+                    assert_eq!(list.get(i), Some(&i));
+                    let expected = if i % 3 == 0 { i * 100 } else { i };
+                    assert_eq!(updated.get(i), Some(&expected));
+                }
+            }
+
+            #[test]
+            #[should_panic = "index out of bounds"]
+            fn set_out_of_bounds() {
+                let list = List::new().prepend(1);
+                let _ = list.set(1, 2);
+            }
+
+            #[test]
+            fn iter() {
+                for len in 0..40 {
+                    let list: List<usize> = (0..len).rev().collect();
+                    let iter = list.iter();
+                    assert_eq!(iter.len(), len);
+                    assert_eq!(iter.copied().collect::<Vec<_>>(), (0..len).collect::<Vec<_>>());
+                }
+            }
+        }
+    }
+}
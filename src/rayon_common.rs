@@ -0,0 +1,47 @@
+/// Defines a parallel iterator that wraps a [`rayon::vec::IntoIter`] of the list's elements or references.
+///
+/// Linked lists cannot be split in the middle without walking them,
+/// so each iterator walks its list once to fill a `Vec`, which rayon can split evenly.
+// Unused when neither `stack` nor `persistent_arc` is enabled.
+#[allow(unused_macros)]
+macro_rules! par_iter {
+    ($(#[$doc:meta])* $name: ident<$($lt: lifetime,)? $t: ident>, $item: ty, $bound: path) => {
+        $(#[$doc])*
+        #[must_use = "iterators are lazy and do nothing unless consumed"]
+        pub struct $name<$($lt,)? $t: $bound> {
+            inner: rayon::vec::IntoIter<$item>,
+        }
+
+        impl<$($lt,)? $t: $bound> rayon::iter::ParallelIterator for $name<$($lt,)? $t> {
+            type Item = $item;
+
+            fn drive_unindexed<C>(self, consumer: C) -> C::Result
+            where
+                C: rayon::iter::plumbing::UnindexedConsumer<Self::Item>,
+            {
+                self.inner.drive_unindexed(consumer)
+            }
+
+            fn opt_len(&self) -> Option<usize> {
+                self.inner.opt_len()
+            }
+        }
+
+        impl<$($lt,)? $t: $bound> rayon::iter::IndexedParallelIterator for $name<$($lt,)? $t> {
+            fn drive<C: rayon::iter::plumbing::Consumer<Self::Item>>(self, consumer: C) -> C::Result {
+                self.inner.drive(consumer)
+            }
+
+            fn len(&self) -> usize {
+                self.inner.len()
+            }
+
+            fn with_producer<CB>(self, callback: CB) -> CB::Output
+            where
+                CB: rayon::iter::plumbing::ProducerCallback<Self::Item>,
+            {
+                self.inner.with_producer(callback)
+            }
+        }
+    };
+}
@@ -0,0 +1,874 @@
+//! An ordered map and set built on a skip list.
+//!
+//! A skip list is a stack of sorted linked lists, where each level skips over
+//! more of the elements than the one below it. This gives expected `O(log n)`
+//! lookups, insertions, and removals, while still allowing cheap in-order iteration.
+//!
+//! The height of each node is chosen randomly. Since this crate is `no_std`,
+//! there is no source of entropy, so every map starts from a fixed seed unless
+//! one is given with [`SkipMap::with_seed`]. This makes the shape of the list
+//! (and so its performance) fully deterministic for a given sequence of operations.
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::iter::FusedIterator;
+use core::ops::{Bound, RangeBounds};
+use core::fmt;
+
+version!{0, 1, 0;
+    thread_safe: true,
+    persistent: false,
+    complexity: {
+        get: "expected O(log n)",
+        insert: "expected O(log n)",
+        remove: "expected O(log n)",
+        first: "O(1)",
+        last: "expected O(log n)",
+        len: "O(1)",
+    },
+}
+
+/// The maximum number of levels a skip list can have.
+const MAX_LEVEL: usize = 32;
+
+/// The seed used by [`SkipMap::new`] and [`SkipSet::new`].
+const DEFAULT_SEED: u64 = 0x2545_F491_4F6C_DD1D;
+
+/// A small xorshift64* generator used to pick node heights.
+#[derive(Clone)]
+struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        // xorshift gets stuck on a state of 0
+        Rng { state: if seed == 0 { DEFAULT_SEED } else { seed } }
+    }
+
+    fn next(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+}
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    next: Vec<Option<usize>>,
+}
+
+/// An ordered map based on a skip list.
+/// See the [module-level documentation](self) for more.
+pub struct SkipMap<K, V> {
+    nodes: Vec<Option<Node<K, V>>>,
+    free: Vec<usize>,
+    head: Vec<Option<usize>>,
+    len: usize,
+    rng: Rng,
+}
+
+impl<K, V> SkipMap<K, V> {
+    /// Creates a new, empty map.
+    pub fn new() -> Self {
+        Self::with_seed(DEFAULT_SEED)
+    }
+
+    /// Creates a new, empty map that uses `seed` to pick node heights.
+    pub fn with_seed(seed: u64) -> Self {
+        SkipMap {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            head: Vec::new(),
+            len: 0,
+            rng: Rng::new(seed),
+        }
+    }
+
+    /// Returns the number of elements in the map.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the map is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Removes all elements from the map.
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.free.clear();
+        self.head.clear();
+        self.len = 0;
+    }
+
+    /// Returns the first key-value pair in the map, which has the minimum key.
+    pub fn first(&self) -> Option<(&K, &V)> {
+        self.head.first()
+            .copied()
+            .flatten()
+            .map(|idx| self.entry(idx))
+    }
+
+    /// Returns the last key-value pair in the map, which has the maximum key.
+    pub fn last(&self) -> Option<(&K, &V)> {
+        let mut current = None;
+        for level in (0..self.head.len()).rev() {
+            while let Some(next) = self.next_of(current, level) {
+                current = Some(next);
+            }
+        }
+        current.map(|idx| self.entry(idx))
+    }
+
+    /// Removes and returns the first key-value pair in the map.
+    pub fn pop_first(&mut self) -> Option<(K, V)> {
+        let idx = self.head.first().copied().flatten()?;
+        let node = self.take_node(idx);
+        for (level, next) in node.next.into_iter().enumerate() {
+            self.head[level] = next;
+        }
+        self.trim_levels();
+        Some((node.key, node.value))
+    }
+
+    /// Creates an iterator over the key-value pairs of the map, in ascending order of keys.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            range: Range {
+                map: self,
+                next: self.head.first().copied().flatten(),
+                end: None,
+            },
+            len: self.len,
+        }
+    }
+
+    fn node(&self, idx: usize) -> &Node<K, V> {
+        self.nodes[idx].as_ref().expect("skip list links to a free slot")
+    }
+
+    fn node_mut(&mut self, idx: usize) -> &mut Node<K, V> {
+        self.nodes[idx].as_mut().expect("skip list links to a free slot")
+    }
+
+    fn entry(&self, idx: usize) -> (&K, &V) {
+        let node = self.node(idx);
+        (&node.key, &node.value)
+    }
+
+    /// Gets the next node after `current` (or the head, if `None`) on `level`.
+    fn next_of(&self, current: Option<usize>, level: usize) -> Option<usize> {
+        match current {
+            None => self.head[level],
+            Some(idx) => self.node(idx).next[level],
+        }
+    }
+
+    fn set_next(&mut self, current: Option<usize>, level: usize, next: Option<usize>) {
+        match current {
+            None => self.head[level] = next,
+            Some(idx) => self.node_mut(idx).next[level] = next,
+        }
+    }
+
+    fn take_node(&mut self, idx: usize) -> Node<K, V> {
+        let node = self.nodes[idx].take().expect("skip list links to a free slot");
+        self.free.push(idx);
+        self.len -= 1;
+        node
+    }
+
+    /// Removes empty levels from the top of the list.
+    fn trim_levels(&mut self) {
+        while self.head.last() == Some(&None) {
+            self.head.pop();
+        }
+    }
+
+    fn random_level(&mut self) -> usize {
+        let level = self.rng.next().trailing_ones() as usize + 1;
+        // Never grow by more than one level at a time.
+        level.min(self.head.len() + 1).min(MAX_LEVEL)
+    }
+
+    /// Finds, on each level, the last node that `goes_before` the key.
+    fn predecessors(
+        &self,
+        mut goes_before: impl FnMut(&K) -> bool,
+    ) -> [Option<usize>; MAX_LEVEL] {
+        let mut update = [None; MAX_LEVEL];
+        let mut current = None;
+        for level in (0..self.head.len()).rev() {
+            while let Some(next) = self.next_of(current, level) {
+                if !goes_before(&self.node(next).key) { break }
+                current = Some(next);
+            }
+            update[level] = current;
+        }
+        update
+    }
+
+    /// Finds the first node for which `goes_before` returns `false`.
+    fn lower_bound(&self, goes_before: impl FnMut(&K) -> bool) -> Option<usize> {
+        if self.head.is_empty() { return None }
+        let update = self.predecessors(goes_before);
+        self.next_of(update[0], 0)
+    }
+}
+
+impl<K: Ord, V> SkipMap<K, V> {
+    /// Returns a reference to the value corresponding to the key.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.find(key).map(|idx| &self.node(idx).value)
+    }
+
+    /// Returns the key-value pair corresponding to the key.
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.find(key).map(|idx| self.entry(idx))
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.find(key).map(|idx| &mut self.node_mut(idx).value)
+    }
+
+    /// Returns whether the map contains a value for the key.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.find(key).is_some()
+    }
+
+    /// Inserts a key-value pair into the map.
+    ///
+    /// If the map already had this key present, the value is updated,
+    /// and the old value is returned. The key itself is not updated.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let mut update = self.predecessors(|k| k < &key);
+
+        let found = if self.head.is_empty() { None } else { self.next_of(update[0], 0) };
+        if let Some(idx) = found {
+            let node = self.node_mut(idx);
+            if node.key == key {
+                return Some(core::mem::replace(&mut node.value, value));
+            }
+        }
+
+        let height = self.random_level();
+        while self.head.len() < height {
+            update[self.head.len()] = None;
+            self.head.push(None);
+        }
+
+        let next = (0..height)
+            .map(|level| self.next_of(update[level], level))
+            .collect();
+        let node = Node { key, value, next };
+
+        let idx = if let Some(idx) = self.free.pop() {
+            self.nodes[idx] = Some(node);
+            idx
+        } else {
+            self.nodes.push(Some(node));
+            self.nodes.len() - 1
+        };
+
+        for (level, &prev) in update.iter().enumerate().take(height) {
+            self.set_next(prev, level, Some(idx));
+        }
+        self.len += 1;
+        None
+    }
+
+    /// Removes a key from the map, returning the value at the key if it was in the map.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    /// Removes a key from the map, returning the stored key and value if it was in the map.
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        if self.head.is_empty() { return None }
+        let update = self.predecessors(|k| k.borrow() < key);
+        let idx = self.next_of(update[0], 0)
+            .filter(|&idx| self.node(idx).key.borrow() == key)?;
+
+        let node = self.take_node(idx);
+        for (level, next) in node.next.into_iter().enumerate() {
+            self.set_next(update[level], level, next);
+        }
+        self.trim_levels();
+        Some((node.key, node.value))
+    }
+
+    /// Creates an iterator over a sub-range of the key-value pairs of the map,
+    /// in ascending order of keys.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than its end,
+    /// or if the start and end are equal and both excluded.
+    pub fn range<Q, R>(&self, range: R) -> Range<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let (start, end) = (range.start_bound(), range.end_bound());
+        if let (Bound::Included(s) | Bound::Excluded(s), Bound::Included(e) | Bound::Excluded(e)) = (start, end) {
+            match s.cmp(e) {
+                Ordering::Greater => panic!("range start is greater than range end in SkipMap"),
+                Ordering::Equal if matches!((start, end), (Bound::Excluded(_), Bound::Excluded(_))) => {
+                    panic!("range start and end are equal and excluded in SkipMap")
+                }
+                _ => {}
+            }
+        }
+
+        let next = match start {
+            Bound::Included(s) => self.lower_bound(|k| k.borrow() < s),
+            Bound::Excluded(s) => self.lower_bound(|k| k.borrow() <= s),
+            Bound::Unbounded => self.head.first().copied().flatten(),
+        };
+        let end = match end {
+            Bound::Included(e) => self.lower_bound(|k| k.borrow() <= e),
+            Bound::Excluded(e) => self.lower_bound(|k| k.borrow() < e),
+            Bound::Unbounded => None,
+        };
+
+        Range { map: self, next, end }
+    }
+
+    fn find<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.lower_bound(|k| k.borrow() < key)
+            .filter(|&idx| self.node(idx).key.borrow() == key)
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for SkipMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self).finish()
+    }
+}
+
+impl<K, V> Default for SkipMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord, V> Extend<(K, V)> for SkipMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for SkipMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = SkipMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a SkipMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+impl<K, V> IntoIterator for SkipMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> IntoIter<K, V> {
+        IntoIter { map: self }
+    }
+}
+
+/// An iterator over a sub-range of the entries of a [`SkipMap`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Range<'a, K, V> {
+    map: &'a SkipMap<K, V>,
+    next: Option<usize>,
+    end: Option<usize>,
+}
+
+impl<'a, K, V> Iterator for Range<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let idx = self.next.filter(|&idx| Some(idx) != self.end)?;
+        let node = self.map.node(idx);
+        self.next = node.next[0];
+        Some((&node.key, &node.value))
+    }
+}
+
+impl<K, V> FusedIterator for Range<'_, K, V> {}
+
+/// An iterator over the entries of a [`SkipMap`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Iter<'a, K, V> {
+    range: Range<'a, K, V>,
+    len: usize,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.range.next()?;
+        self.len -= 1;
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<K, V> FusedIterator for Iter<'_, K, V> {}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+/// An iterator that consumes a [`SkipMap`] and yields its entries.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct IntoIter<K, V> {
+    map: SkipMap<K, V>,
+}
+
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.map.pop_first()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.map.len(), Some(self.map.len()))
+    }
+}
+
+impl<K, V> FusedIterator for IntoIter<K, V> {}
+
+impl<K, V> ExactSizeIterator for IntoIter<K, V> {}
+
+/// An ordered set based on a skip list.
+/// See the [module-level documentation](self) for more.
+pub struct SkipSet<T> {
+    map: SkipMap<T, ()>,
+}
+
+impl<T> SkipSet<T> {
+    /// Creates a new, empty set.
+    pub fn new() -> Self {
+        SkipSet { map: SkipMap::new() }
+    }
+
+    /// Creates a new, empty set that uses `seed` to pick node heights.
+    pub fn with_seed(seed: u64) -> Self {
+        SkipSet { map: SkipMap::with_seed(seed) }
+    }
+
+    /// Returns the number of elements in the set.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns whether the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Removes all elements from the set.
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// Returns the minimum element of the set.
+    pub fn first(&self) -> Option<&T> {
+        self.map.first().map(|(elem, ())| elem)
+    }
+
+    /// Returns the maximum element of the set.
+    pub fn last(&self) -> Option<&T> {
+        self.map.last().map(|(elem, ())| elem)
+    }
+
+    /// Removes and returns the minimum element of the set.
+    pub fn pop_first(&mut self) -> Option<T> {
+        self.map.pop_first().map(|(elem, ())| elem)
+    }
+
+    /// Creates an iterator over the elements of the set, in ascending order.
+    pub fn iter(&self) -> SetIter<'_, T> {
+        SetIter { iter: self.map.iter() }
+    }
+}
+
+impl<T: Ord> SkipSet<T> {
+    /// Adds an element to the set.
+    ///
+    /// Returns whether the element was newly inserted.
+    pub fn insert(&mut self, elem: T) -> bool {
+        self.map.insert(elem, ()).is_none()
+    }
+
+    /// Removes an element from the set, returning whether it was present.
+    pub fn remove<Q>(&mut self, elem: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.remove(elem).is_some()
+    }
+
+    /// Removes and returns the element in the set equal to `elem`, if there is one.
+    pub fn take<Q>(&mut self, elem: &Q) -> Option<T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.remove_entry(elem).map(|(elem, ())| elem)
+    }
+
+    /// Returns a reference to the element in the set equal to `elem`, if there is one.
+    pub fn get<Q>(&self, elem: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.get_key_value(elem).map(|(elem, ())| elem)
+    }
+
+    /// Returns whether the set contains an element equal to `elem`.
+    pub fn contains<Q>(&self, elem: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.map.contains_key(elem)
+    }
+
+    /// Creates an iterator over a sub-range of the elements of the set, in ascending order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than its end,
+    /// or if the start and end are equal and both excluded.
+    pub fn range<Q, R>(&self, range: R) -> SetRange<'_, T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        SetRange { range: self.map.range(range) }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for SkipSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self).finish()
+    }
+}
+
+impl<T> Default for SkipSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> Extend<T> for SkipSet<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.map.extend(iter.into_iter().map(|elem| (elem, ())));
+    }
+}
+
+impl<T: Ord> FromIterator<T> for SkipSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = SkipSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SkipSet<T> {
+    type Item = &'a T;
+    type IntoIter = SetIter<'a, T>;
+
+    fn into_iter(self) -> SetIter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<T> IntoIterator for SkipSet<T> {
+    type Item = T;
+    type IntoIter = SetIntoIter<T>;
+
+    fn into_iter(self) -> SetIntoIter<T> {
+        SetIntoIter { iter: self.map.into_iter() }
+    }
+}
+
+/// An iterator over the elements of a [`SkipSet`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct SetIter<'a, T> {
+    iter: Iter<'a, T, ()>,
+}
+
+impl<'a, T> Iterator for SetIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(elem, ())| elem)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T> FusedIterator for SetIter<'_, T> {}
+
+impl<T> ExactSizeIterator for SetIter<'_, T> {}
+
+/// An iterator over a sub-range of the elements of a [`SkipSet`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct SetRange<'a, T> {
+    range: Range<'a, T, ()>,
+}
+
+impl<'a, T> Iterator for SetRange<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range.next().map(|(elem, ())| elem)
+    }
+}
+
+impl<T> FusedIterator for SetRange<'_, T> {}
+
+/// An iterator that consumes a [`SkipSet`] and yields its elements.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct SetIntoIter<T> {
+    iter: IntoIter<T, ()>,
+}
+
+impl<T> Iterator for SetIntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(elem, ())| elem)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T> FusedIterator for SetIntoIter<T> {}
+
+impl<T> ExactSizeIterator for SetIntoIter<T> {}
+
+#[allow(dead_code)]
+fn assert_properties() {
+    fn is_thread_safe<T: Send + Sync>() {}
+
+    is_thread_safe::<SkipMap<i32, i32>>();
+    is_thread_safe::<SkipSet<i32>>();
+    is_thread_safe::<Iter<i32, i32>>();
+    is_thread_safe::<Range<i32, i32>>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SkipMap, SkipSet};
+    use alloc::vec::Vec;
+    use alloc::vec;
+
+    #[test]
+    fn insert_get() {
+        let mut map = SkipMap::new();
+        assert_eq!(map.get(&1), None);
+
+        assert_eq!(map.insert(2, "two"), None);
+        assert_eq!(map.insert(1, "one"), None);
+        assert_eq!(map.insert(3, "three"), None);
+        assert_eq!(map.insert(2, "TWO"), Some("two"));
+
+        assert_eq!(map.len(), 3);
+        assert_eq!(map.get(&1), Some(&"one"));
+        assert_eq!(map.get(&2), Some(&"TWO"));
+        assert_eq!(map.get(&4), None);
+
+        if let Some(value) = map.get_mut(&3) {
+            *value = "drei";
+        }
+        assert_eq!(map.get(&3), Some(&"drei"));
+    }
+
+    #[test]
+    fn remove() {
+        let mut map: SkipMap<i32, i32> = (0..100).map(|i| (i, i * 10)).collect();
+
+        for i in (0..100).step_by(2) {
+            assert_eq!(map.remove(&i), Some(i * 10));
+        }
+        assert_eq!(map.remove(&0), None);
+        assert_eq!(map.len(), 50);
+
+        let keys: Vec<_> = map.iter().map(|(&k, _)| k).collect();
+        assert_eq!(keys, (1..100).step_by(2).collect::<Vec<_>>());
+
+        // Freed slots get reused
+        map.insert(4, 40);
+        assert_eq!(map.get(&4), Some(&40));
+        assert_eq!(map.len(), 51);
+    }
+
+    #[test]
+    fn first_last_pop_first() {
+        let mut map = SkipMap::new();
+        assert_eq!(map.first(), None);
+        assert_eq!(map.last(), None);
+        assert_eq!(map.pop_first(), None);
+
+        for i in [5, 3, 8, 1, 9] {
+            map.insert(i, ());
+        }
+
+        assert_eq!(map.first(), Some((&1, &())));
+        assert_eq!(map.last(), Some((&9, &())));
+
+        let popped: Vec<_> = core::iter::from_fn(|| map.pop_first()).map(|(k, ())| k).collect();
+        assert_eq!(popped, vec![1, 3, 5, 8, 9]);
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn range() {
+        let map: SkipMap<i32, ()> = (0..20).map(|i| (i * 2, ())).collect();
+        let keys = |iter: super::Range<'_, i32, ()>| iter.map(|(&k, ())| k).collect::<Vec<_>>();
+
+        assert_eq!(keys(map.range(4..10)), vec![4, 6, 8]);
+        assert_eq!(keys(map.range(3..=10)), vec![4, 6, 8, 10]);
+        assert_eq!(keys(map.range(35..)), vec![36, 38]);
+        assert_eq!(keys(map.range(..3)), vec![0, 2]);
+        assert_eq!(keys(map.range(5..5)), Vec::<i32>::new());
+        assert_eq!(keys(map.range(100..)), Vec::<i32>::new());
+        assert_eq!(keys(map.range(..)).len(), 20);
+    }
+
+    #[test]
+    #[should_panic = "range start is greater than range end"]
+    fn range_backwards() {
+        use core::ops::Bound;
+
+        let map: SkipMap<i32, ()> = SkipMap::new();
+        let _ = map.range((Bound::Included(5), Bound::Excluded(2)));
+    }
+
+    #[test]
+    fn deterministic() {
+        let shape = |seed| {
+            let mut map = SkipMap::with_seed(seed);
+            map.extend((0..64).map(|i| (i, ())));
+            map.nodes.iter()
+                .map(|node| node.as_ref().unwrap().next.len())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(shape(7), shape(7));
+        assert_ne!(shape(7), shape(8));
+    }
+
+    #[test]
+    fn matches_btree_map() {
+        use alloc::collections::BTreeMap;
+
+        let mut map = SkipMap::with_seed(42);
+        let mut model = BTreeMap::new();
+        let mut state = 1u32;
+
+        for _ in 0..2000 {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            let key = (state >> 16) % 200;
+            if state.is_multiple_of(3) {
+                assert_eq!(map.remove(&key), model.remove(&key));
+            } else {
+                assert_eq!(map.insert(key, state), model.insert(key, state));
+            }
+        }
+
+        assert_eq!(map.len(), model.len());
+        assert!(map.iter().eq(model.iter()));
+        assert!(map.range(50..150).eq(model.range(50..150)));
+        assert_eq!(map.last(), model.last_key_value());
+    }
+
+    #[test]
+    fn set() {
+        let mut set = SkipSet::with_seed(3);
+        assert!(set.insert(3));
+        assert!(set.insert(1));
+        assert!(!set.insert(3));
+        assert!(set.insert(2));
+
+        assert!(set.contains(&2));
+        assert_eq!(set.get(&1), Some(&1));
+        assert_eq!(set.first(), Some(&1));
+        assert_eq!(set.last(), Some(&3));
+        assert_eq!(set.range(2..).copied().collect::<Vec<_>>(), vec![2, 3]);
+
+        assert!(set.remove(&2));
+        assert!(!set.remove(&2));
+        assert_eq!(set.pop_first(), Some(1));
+        assert_eq!(set.into_iter().collect::<Vec<_>>(), vec![3]);
+    }
+
+    #[test]
+    fn debug_fmt() {
+        use alloc::format;
+
+        let map: SkipMap<i32, char> = [(2, 'b'), (1, 'a')].into_iter().collect();
+        assert_eq!(format!("{map:?}"), "{1: 'a', 2: 'b'}");
+
+        let set: SkipSet<i32> = [3, 1, 2].into_iter().collect();
+        assert_eq!(format!("{set:?}"), "{1, 2, 3}");
+    }
+}
@@ -0,0 +1,624 @@
+//! Singly linked lists that keep their elements in sorted order.
+//!
+//! [`SortedList`] orders its elements by their [`Ord`] implementation,
+//! and [`SortedByKey`] orders them by a key computed from each element.
+//! Elements that compare equal stay in the order they were inserted in.
+use alloc::boxed::Box;
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::iter::FusedIterator;
+use core::ops::{Bound, RangeBounds};
+use core::fmt;
+
+version!{0, 1, 0;
+    thread_safe: true,
+    persistent: false,
+    complexity: {
+        insert: "O(n)",
+        remove: "O(n)",
+        contains: "O(n)",
+        peek_min: "O(1)",
+        pop_min: "O(1)",
+        merge: "O(n + m)",
+        len: "O(n)",
+    },
+}
+
+type Link<T> = Option<Box<Node<T>>>;
+
+struct Node<T> {
+    elem: T,
+    next: Link<T>,
+}
+
+/// A singly linked list that keeps its elements sorted in ascending order.
+/// See the [module-level documentation](self) for more.
+pub struct SortedList<T> {
+    head: Link<T>,
+}
+
+/// A singly linked list that keeps its elements sorted in ascending order of a key.
+/// See the [module-level documentation](self) for more.
+pub struct SortedByKey<T, F> {
+    head: Link<T>,
+    key: F,
+}
+
+/// Inserts `elem` before the first element that `goes_before(element, &elem)` returns `false` for.
+fn insert_by<T>(mut link: &mut Link<T>, elem: T, mut goes_before: impl FnMut(&T, &T) -> bool) {
+    while link.as_ref().is_some_and(|node| goes_before(&node.elem, &elem)) {
+        link = &mut link.as_mut().unwrap().next;
+    }
+    let next = link.take();
+    *link = Some(Box::new(Node { elem, next }));
+}
+
+/// Removes the first element that `cmp` returns `Equal` for,
+/// giving up at the first element it returns `Greater` for.
+fn remove_by<T>(mut link: &mut Link<T>, mut cmp: impl FnMut(&T) -> Ordering) -> Option<T> {
+    loop {
+        match link.as_ref().map(|node| cmp(&node.elem)) {
+            Some(Ordering::Less) => link = &mut link.as_mut().unwrap().next,
+            Some(Ordering::Equal) => {
+                let node = link.take().unwrap();
+                *link = node.next;
+                return Some(node.elem);
+            }
+            Some(Ordering::Greater) | None => return None,
+        }
+    }
+}
+
+fn find_by<T>(mut current: Option<&Node<T>>, mut cmp: impl FnMut(&T) -> Ordering) -> Option<&T> {
+    while let Some(node) = current {
+        match cmp(&node.elem) {
+            Ordering::Less => current = node.next.as_deref(),
+            Ordering::Equal => return Some(&node.elem),
+            Ordering::Greater => return None,
+        }
+    }
+    None
+}
+
+/// Merges two sorted lists by relinking their nodes.
+/// `left_first` decides whether an element of `left` goes before an element of `right`.
+fn merge_by<T>(mut left: Link<T>, mut right: Link<T>, mut left_first: impl FnMut(&T, &T) -> bool) -> Link<T> {
+    let mut head = None;
+    let mut tail = &mut head;
+    loop {
+        let node = match (left, right) {
+            (Some(mut l), Some(r)) if left_first(&l.elem, &r.elem) => {
+                (left, right) = (l.next.take(), Some(r));
+                l
+            }
+            (l, Some(mut r)) => {
+                (left, right) = (l, r.next.take());
+                r
+            }
+            (rest, None) => {
+                *tail = rest;
+                return head;
+            }
+        };
+        tail = &mut tail.insert(node).next;
+    }
+}
+
+/// Finds the elements from the first one `before_start` returns `false` for,
+/// up to (not including) the first one after that `before_end` returns `false` for.
+fn range_by<T>(
+    head: Option<&Node<T>>,
+    mut before_start: impl FnMut(&T) -> bool,
+    mut before_end: impl FnMut(&T) -> bool,
+) -> Range<'_, T> {
+    let mut start = head;
+    while let Some(node) = start.filter(|node| before_start(&node.elem)) {
+        start = node.next.as_deref();
+    }
+
+    let remaining = Iter { next: start }.take_while(|elem| before_end(elem)).count();
+    Range { next: start, remaining }
+}
+
+/// Returns whether `elem` is before the start of the range starting at `start`.
+fn before_start<Q: Ord + ?Sized>(elem: &Q, start: Bound<&Q>) -> bool {
+    match start {
+        Bound::Included(start) => elem < start,
+        Bound::Excluded(start) => elem <= start,
+        Bound::Unbounded => false,
+    }
+}
+
+/// Returns whether `elem` is before the end of the range ending at `end`.
+fn before_end<Q: Ord + ?Sized>(elem: &Q, end: Bound<&Q>) -> bool {
+    match end {
+        Bound::Included(end) => elem <= end,
+        Bound::Excluded(end) => elem < end,
+        Bound::Unbounded => true,
+    }
+}
+
+fn clear<T>(head: &mut Link<T>) {
+    let mut current = head.take();
+
+    while let Some(mut node) = current {
+        current = node.next.take();
+    }
+}
+
+fn pop<T>(head: &mut Link<T>) -> Option<T> {
+    head.take().map(|node| {
+        *head = node.next;
+        node.elem
+    })
+}
+
+impl<T> SortedList<T> {
+    /// Creates a new list.
+    pub const fn new() -> Self {
+        SortedList { head: None }
+    }
+
+    /// Returns whether the list is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    /// Returns the length of the list.
+    pub fn len(&self) -> usize {
+        self.iter().len()
+    }
+
+    /// Removes all elements from the list.
+    pub fn clear(&mut self) {
+        clear(&mut self.head);
+    }
+
+    /// Returns a reference to the minimum element in the list.
+    pub fn peek_min(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.elem)
+    }
+
+    /// Removes the minimum element from the list.
+    pub fn pop_min(&mut self) -> Option<T> {
+        pop(&mut self.head)
+    }
+
+    /// Creates an iterator over shared references to each element in the list, in ascending order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { next: self.head.as_deref() }
+    }
+}
+
+impl<T: Ord> SortedList<T> {
+    /// Inserts an element into its sorted position in the list,
+    /// after any elements that are equal to it.
+    pub fn insert(&mut self, elem: T) {
+        insert_by(&mut self.head, elem, |other, elem| other <= elem);
+    }
+
+    /// Removes the first element in the list that is equal to `elem`, and returns it.
+    pub fn remove<Q>(&mut self, elem: &Q) -> Option<T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        remove_by(&mut self.head, |other| other.borrow().cmp(elem))
+    }
+
+    /// Returns whether the list contains an element equal to `elem`.
+    pub fn contains<Q>(&self, elem: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        find_by(self.head.as_deref(), |other| other.borrow().cmp(elem)).is_some()
+    }
+
+    /// Moves all elements of `other` into `self`, keeping them sorted.
+    ///
+    /// This takes `O(n + m)` time, and does not allocate.
+    /// Elements of `self` go before elements of `other` that are equal to them.
+    pub fn merge(&mut self, mut other: SortedList<T>) {
+        self.head = merge_by(self.head.take(), other.head.take(), |l, r| l <= r);
+    }
+
+    /// Creates an iterator over the elements of the list that are in `range`, in ascending order.
+    pub fn range<Q, R>(&self, range: R) -> Range<'_, T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        range_by(
+            self.head.as_deref(),
+            |elem| before_start(elem.borrow(), range.start_bound()),
+            |elem| before_end(elem.borrow(), range.end_bound()),
+        )
+    }
+}
+
+impl<T, K: Ord, F: Fn(&T) -> K> SortedByKey<T, F> {
+    /// Creates a new list that is sorted by the keys that `key` returns.
+    pub const fn new(key: F) -> Self {
+        SortedByKey { head: None, key }
+    }
+
+    /// Inserts an element into its sorted position in the list,
+    /// after any elements that have keys equal to its key.
+    pub fn insert(&mut self, elem: T) {
+        let elem_key = (self.key)(&elem);
+        insert_by(&mut self.head, elem, |other, _| (self.key)(other) <= elem_key);
+    }
+
+    /// Removes the first element in the list with a key equal to `key`, and returns it.
+    pub fn remove(&mut self, key: &K) -> Option<T> {
+        remove_by(&mut self.head, |other| (self.key)(other).cmp(key))
+    }
+
+    /// Returns a reference to the first element in the list with a key equal to `key`.
+    pub fn get(&self, key: &K) -> Option<&T> {
+        find_by(self.head.as_deref(), |other| (self.key)(other).cmp(key))
+    }
+
+    /// Returns whether the list contains an element with a key equal to `key`.
+    pub fn contains(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Moves all elements of `other` into `self`, keeping them sorted.
+    ///
+    /// This takes `O(n + m)` time, and does not allocate.
+    /// Elements of `self` go before elements of `other` with keys equal to theirs.
+    pub fn merge(&mut self, mut other: Self) {
+        self.head = merge_by(self.head.take(), other.head.take(), |l, r| (self.key)(l) <= (self.key)(r));
+    }
+
+    /// Creates an iterator over the elements of the list with keys in `range`, in ascending order.
+    pub fn range<R: RangeBounds<K>>(&self, range: R) -> Range<'_, T> {
+        range_by(
+            self.head.as_deref(),
+            |elem| before_start(&(self.key)(elem), range.start_bound()),
+            |elem| before_end(&(self.key)(elem), range.end_bound()),
+        )
+    }
+}
+
+impl<T, F> SortedByKey<T, F> {
+    /// Returns whether the list is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    /// Returns the length of the list.
+    pub fn len(&self) -> usize {
+        self.iter().len()
+    }
+
+    /// Removes all elements from the list.
+    pub fn clear(&mut self) {
+        clear(&mut self.head);
+    }
+
+    /// Returns a reference to the element with the minimum key in the list.
+    pub fn peek_min(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.elem)
+    }
+
+    /// Removes the element with the minimum key from the list.
+    pub fn pop_min(&mut self) -> Option<T> {
+        pop(&mut self.head)
+    }
+
+    /// Creates an iterator over shared references to each element in the list, in ascending order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { next: self.head.as_deref() }
+    }
+}
+
+impl<T: Ord> Extend<T> for SortedList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for elem in iter {
+            self.insert(elem);
+        }
+    }
+}
+
+impl<T, K: Ord, F: Fn(&T) -> K> Extend<T> for SortedByKey<T, F> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for elem in iter {
+            self.insert(elem);
+        }
+    }
+}
+
+impl<T: Ord> FromIterator<T> for SortedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = SortedList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SortedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T, F> IntoIterator for &'a SortedByKey<T, F> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<T> IntoIterator for SortedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(mut self) -> IntoIter<T> {
+        IntoIter { head: self.head.take() }
+    }
+}
+
+impl<T, F> IntoIterator for SortedByKey<T, F> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(mut self) -> IntoIter<T> {
+        IntoIter { head: self.head.take() }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for SortedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T: fmt::Debug, F> fmt::Debug for SortedByKey<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T> Default for SortedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for SortedList<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T, F> Drop for SortedByKey<T, F> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+/// An iterator that yields shared references to the elements of a sorted list.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.take().map(|node| {
+            self.next = node.next.as_deref();
+            &node.elem
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let mut current = self.next;
+        let mut len = 0;
+        while let Some(node) = current {
+            current = node.next.as_deref();
+            len += 1;
+        }
+        (len, Some(len))
+    }
+}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+/// An iterator that yields shared references to the elements of a sorted list that are in a range.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Range<'a, T> {
+    next: Option<&'a Node<T>>,
+    remaining: usize,
+}
+
+impl<'a, T> Iterator for Range<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        self.next.take().map(|node| {
+            self.next = node.next.as_deref();
+            &node.elem
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> FusedIterator for Range<'_, T> {}
+
+impl<T> ExactSizeIterator for Range<'_, T> {}
+
+/// An iterator that consumes a sorted list and yields its elements in ascending order.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct IntoIter<T> {
+    head: Link<T>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        pop(&mut self.head)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        Iter { next: self.head.as_deref() }.size_hint()
+    }
+}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> Drop for IntoIter<T> {
+    fn drop(&mut self) {
+        clear(&mut self.head);
+    }
+}
+
+#[allow(dead_code)]
+fn assert_properties() {
+    fn list_covariant<'a, T>(x: SortedList<&'static T>) -> SortedList<&'a T> { x }
+    fn iter_covariant<'i, 'a, T>(x: Iter<'i, &'static T>) -> Iter<'i, &'a T> { x }
+
+    fn is_thread_safe<T: Send + Sync>() {}
+
+    is_thread_safe::<SortedList<i32>>();
+    is_thread_safe::<Iter<i32>>();
+    is_thread_safe::<Range<i32>>();
+    is_thread_safe::<IntoIter<i32>>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SortedList, SortedByKey};
+    use core::ops::Bound;
+    use alloc::vec::Vec;
+    use alloc::vec;
+
+    #[test]
+    fn insert_pop_min() {
+        let mut list = SortedList::new();
+        assert_eq!(list.pop_min(), None);
+
+        for i in [5, 1, 4, 2, 3] {
+            list.insert(i);
+        }
+
+        assert_eq!(list.len(), 5);
+        assert_eq!(list.peek_min(), Some(&1));
+        assert_eq!(list.pop_min(), Some(1));
+        assert_eq!(list.pop_min(), Some(2));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn remove_contains() {
+        let mut list: SortedList<i32> = [3, 1, 2, 2].into_iter().collect();
+
+        assert!(list.contains(&2));
+        assert!(!list.contains(&7));
+        assert_eq!(list.remove(&2), Some(2));
+        assert!(list.contains(&2));
+        assert_eq!(list.remove(&2), Some(2));
+        assert!(!list.contains(&2));
+        assert_eq!(list.remove(&2), None);
+        assert_eq!(list.remove(&0), None);
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![1, 3]);
+    }
+
+    #[test]
+    fn stable() {
+        let mut list = SortedByKey::new(|&(key, _): &(i32, char)| key);
+        list.extend([(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd')]);
+
+        assert_eq!(
+            list.iter().map(|&(_, c)| c).collect::<Vec<_>>(),
+            vec!['b', 'd', 'a', 'c'],
+        );
+        assert_eq!(list.remove(&2), Some((2, 'a')));
+        assert_eq!(list.get(&1), Some(&(1, 'b')));
+    }
+
+    #[test]
+    fn merge() {
+        let mut left: SortedList<i32> = [1, 4, 6, 9].into_iter().collect();
+        let right: SortedList<i32> = [0, 4, 5, 10, 11].into_iter().collect();
+        left.merge(right);
+        assert_eq!(left.iter().copied().collect::<Vec<_>>(), vec![0, 1, 4, 4, 5, 6, 9, 10, 11]);
+
+        left.merge(SortedList::new());
+        assert_eq!(left.len(), 9);
+
+        let mut empty = SortedList::new();
+        empty.merge(left);
+        assert_eq!(empty.len(), 9);
+    }
+
+    #[test]
+    fn merge_by_key() {
+        // Both lists need the same key function, so they share one closure.
+        let key = |s: &&str| s.len();
+        let mut left = SortedByKey::new(key);
+        left.extend(["a", "ccc", "eeeee"]);
+        let mut right = SortedByKey::new(key);
+        right.extend(["bb", "CCC", "dddd"]);
+
+        left.merge(right);
+        assert_eq!(
+            left.iter().copied().collect::<Vec<_>>(),
+            vec!["a", "bb", "ccc", "CCC", "dddd", "eeeee"],
+        );
+    }
+
+    #[test]
+    fn range() {
+        let list: SortedList<i32> = (0..10).rev().collect();
+        let bounds = (Bound::Excluded(2), Bound::Included(5));
+
+        assert_eq!(list.range(bounds).copied().collect::<Vec<_>>(), vec![3, 4, 5]);
+        assert_eq!(list.range(7..).copied().collect::<Vec<_>>(), vec![7, 8, 9]);
+        assert_eq!(list.range(..2).len(), 2);
+        assert_eq!(list.range(20..30).len(), 0);
+
+        let mut by_key = SortedByKey::new(|&x: &i32| x / 10);
+        by_key.extend([5, 15, 25, 12, 35]);
+        assert_eq!(by_key.range(1..=2).copied().collect::<Vec<_>>(), vec![15, 12, 25]);
+    }
+
+    #[test]
+    fn long_drop() {
+        let mut list = SortedList::new();
+        for i in (0..100_000).rev() {
+            list.insert(i);
+        }
+        drop(list);
+    }
+
+    #[test]
+    fn debug_fmt() {
+        use alloc::format;
+
+        let list: SortedList<i32> = [3, 1, 2].into_iter().collect();
+        assert_eq!(format!("{list:?}"), "[1, 2, 3]");
+    }
+}
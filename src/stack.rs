@@ -1,44 +1,111 @@
 //! A singly linked list with stack operations.
-use alloc::boxed::Box;
+//!
+//! A [`List`] allocates each of its nodes in a `Box` from [`allocator_api2`](https://docs.rs/allocator-api2),
+//! which works on stable Rust and lets [`List::try_push`] report allocation failures.
+//! Each node keeps the allocator it was allocated with, so it can be moved to another list safely.
+//! Lists use the [`Global`] allocator, unless the `allocator_api` feature is enabled
+//! and they are created with `List::new_in`.
+pub use allocator_api2::alloc::{Allocator, Global};
+use allocator_api2::boxed::Box;
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use crate::AllocError;
 use core::iter::FusedIterator;
 use core::fmt;
-
-version!{1, 4, 0}
+use core::ops::{Deref, DerefMut};
+
+version!{1, 5, 0;
+    thread_safe: true,
+    persistent: false,
+    complexity: {
+        push: "O(1)",
+        pop: "O(1)",
+        peek: "O(1)",
+        len: "O(n)",
+    },
+}
 
 /// A singly linked list that can do (most of) what a stack can.
-pub struct List<T> {
-    head: Option<Box<Node<T>>>,
+pub struct List<T, A: Allocator = Global> {
+    head: Link<T, A>,
+    alloc: A,
 }
 
-struct Node<T> {
+type Link<T, A> = Option<Box<Node<T, A>, A>>;
+
+struct Node<T, A: Allocator> {
     elem: T,
-    next: Option<Box<Node<T>>>,
+    next: Link<T, A>,
 }
 
 impl<T> List<T> {
     /// Creates a new list.
     pub const fn new() -> Self {
-        List { head: None }
+        List { head: None, alloc: Global }
     }
 
-    /// Pushes an element onto the back of the list.
-    pub fn push(&mut self, elem: T) {
-        let new = Box::new(Node {
-            elem,
-            next: self.head.take(),
-        });
+    /// Creates a list by pushing each element of the iterator onto it, without aborting if allocation fails.
+    ///
+    /// # Errors
+    ///
+    /// If a node cannot be allocated, this returns its element in an [`AllocError`],
+    /// and drops the list that was built so far.
+    pub fn try_from_iter<I: IntoIterator<Item = T>>(iter: I) -> Result<Self, AllocError<T>> {
+        let mut list = List::new();
+        list.try_extend(iter)?;
+        Ok(list)
+    }
+}
 
-        self.head = Some(new);
+impl<T, A: Allocator> List<T, A> {
+    /// Creates a new list that allocates its nodes in `alloc`.
+    #[cfg(feature = "allocator_api")]
+    pub const fn new_in(alloc: A) -> Self {
+        List { head: None, alloc }
+    }
+
+    /// Returns a reference to the allocator of the list.
+    #[cfg(feature = "allocator_api")]
+    pub const fn allocator(&self) -> &A {
+        &self.alloc
     }
 
     /// Removes the first element from the list.
     pub fn pop(&mut self) -> Option<T> {
         self.head.take().map(|node| {
+            let node = Box::into_inner(node);
             self.head = node.next;
             node.elem
         })
     }
 
+    /// Removes the first node from the list without deallocating it.
+    ///
+    /// The node can be pushed back onto a list with [`push_node`](List::push_node),
+    /// which lets nodes be reused without allocating.
+    pub fn pop_node(&mut self) -> Option<DetachedNode<T, A>> {
+        self.head.take().map(|mut node| {
+            self.head = node.next.take();
+            DetachedNode { node }
+        })
+    }
+
+    /// Pushes a node onto the back of the list without allocating.
+    ///
+    /// The node keeps the allocator it was allocated with, which deallocates it later.
+    pub fn push_node(&mut self, node: DetachedNode<T, A>) {
+        let mut node = node.node;
+        node.next = self.head.take();
+        self.head = Some(node);
+    }
+
+    /// Moves the first node of the list onto the back of `other`, without allocating.
+    ///
+    /// Returns `false` if the list is empty.
+    pub fn move_top_to(&mut self, other: &mut List<T, A>) -> bool {
+        self.pop_node().map(|node| other.push_node(node)).is_some()
+    }
+
     /// Returns whether the list is empty.
     pub const fn is_empty(&self) -> bool {
         self.head.is_none()
@@ -69,21 +136,66 @@ impl<T> List<T> {
     }
 
     /// Creates an iterator over shared references to each element in the list.
-    pub fn iter(&self) -> Iter<'_, T> {
+    pub fn iter(&self) -> Iter<'_, T, A> {
         Iter {
             next: self.head.as_deref(),
         }
     }
 
     /// Creates an iterator over mutable references to each element in the list.
-    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+    pub fn iter_mut(&mut self) -> IterMut<'_, T, A> {
         IterMut {
             next: self.head.as_deref_mut(),
         }
     }
 }
 
-impl<T> Extend<T> for List<T> {
+impl<T, A: Allocator + Clone> List<T, A> {
+    /// Pushes an element onto the back of the list.
+    pub fn push(&mut self, elem: T) {
+        let new = Box::new_in(Node {
+            elem,
+            next: self.head.take(),
+        }, self.alloc.clone());
+
+        self.head = Some(new);
+    }
+
+    /// Pushes an element onto the back of the list, without aborting if allocation fails.
+    ///
+    /// # Errors
+    ///
+    /// If the node cannot be allocated, this returns `elem` in an [`AllocError`].
+    pub fn try_push(&mut self, elem: T) -> Result<(), AllocError<T>> {
+        // Allocating before moving `elem` lets it be handed back on failure.
+        let Ok(uninit) = Box::try_new_uninit_in(self.alloc.clone()) else {
+            return Err(AllocError(elem));
+        };
+
+        let new = Box::write(uninit, Node {
+            elem,
+            next: self.head.take(),
+        });
+
+        self.head = Some(new);
+        Ok(())
+    }
+
+    /// Pushes each element of the iterator onto the back of the list, without aborting if allocation fails.
+    ///
+    /// # Errors
+    ///
+    /// If a node cannot be allocated, this returns its element in an [`AllocError`].
+    /// The elements before it stay in the list, and the rest of the iterator is dropped.
+    pub fn try_extend<I: IntoIterator<Item = T>>(&mut self, iter: I) -> Result<(), AllocError<T>> {
+        for elem in iter {
+            self.try_push(elem)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T, A: Allocator + Clone> Extend<T> for List<T, A> {
     fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
         for elem in iter {
             self.push(elem);
@@ -91,7 +203,7 @@ impl<T> Extend<T> for List<T> {
     }
 }
 
-impl<'a, T: 'a + Copy> Extend<&'a T> for List<T> {
+impl<'a, T: 'a + Copy, A: Allocator + Clone> Extend<&'a T> for List<T, A> {
     fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
         self.extend(iter.into_iter().copied());
     }
@@ -105,9 +217,57 @@ impl<T> FromIterator<T> for List<T> {
     }
 }
 
+/// Converts a `Vec` to a list with the same elements in the same order,
+/// so the first element of the `Vec` is the top of the stack.
+impl<T> From<Vec<T>> for List<T> {
+    fn from(elems: Vec<T>) -> Self {
+        elems.into_iter().rev().collect()
+    }
+}
+
+/// Converts a `VecDeque` to a list with the same elements in the same order,
+/// so the front of the `VecDeque` is the top of the stack.
+impl<T> From<VecDeque<T>> for List<T> {
+    fn from(elems: VecDeque<T>) -> Self {
+        elems.into_iter().rev().collect()
+    }
+}
+
+/// Converts a boxed slice to a list with the same elements in the same order,
+/// so the first element of the slice is the top of the stack.
+impl<T> From<alloc::boxed::Box<[T]>> for List<T> {
+    fn from(elems: alloc::boxed::Box<[T]>) -> Self {
+        Self::from(elems.into_vec())
+    }
+}
+
+/// Converts a list to a `Vec` with the same elements in the same order,
+/// so the top of the stack is the first element of the `Vec`.
+impl<T, A: Allocator> From<List<T, A>> for Vec<T> {
+    fn from(list: List<T, A>) -> Self {
+        list.into_iter().collect()
+    }
+}
+
+/// Converts a list to a `VecDeque` with the same elements in the same order,
+/// so the top of the stack is the front of the `VecDeque`.
+impl<T, A: Allocator> From<List<T, A>> for VecDeque<T> {
+    fn from(list: List<T, A>) -> Self {
+        list.into_iter().collect()
+    }
+}
+
+/// Converts a list to a boxed slice with the same elements in the same order,
+/// so the top of the stack is the first element of the slice.
+impl<T, A: Allocator> From<List<T, A>> for alloc::boxed::Box<[T]> {
+    fn from(list: List<T, A>) -> Self {
+        Vec::from(list).into_boxed_slice()
+    }
+}
+
 macro_rules! into_iter_impl {
     ($type: ty, $item: ty, $intoiter: ty, $conv_fn: path) => {
-        impl<'a, T> IntoIterator for $type {
+        impl<'a, T, A: Allocator> IntoIterator for $type {
             type Item = $item;
             type IntoIter = $intoiter;
 
@@ -119,15 +279,15 @@ macro_rules! into_iter_impl {
 }
 
 // This exists due to how into_iter_impl! takes a converter
-fn list_into_iter<T>(list: List<T>) -> IntoIter<T> {
+fn list_into_iter<T, A: Allocator>(list: List<T, A>) -> IntoIter<T, A> {
     IntoIter { list }
 }
 
-into_iter_impl!{List<T>, T, IntoIter<T>, list_into_iter}
-into_iter_impl!{&'a List<T>, &'a T, Iter<'a, T>, List::iter}
-into_iter_impl!{&'a mut List<T>, &'a mut T, IterMut<'a, T>, List::iter_mut}
+into_iter_impl!{List<T, A>, T, IntoIter<T, A>, list_into_iter}
+into_iter_impl!{&'a List<T, A>, &'a T, Iter<'a, T, A>, List::iter}
+into_iter_impl!{&'a mut List<T, A>, &'a mut T, IterMut<'a, T, A>, List::iter_mut}
 
-impl<T: fmt::Debug> fmt::Debug for List<T> {
+impl<T: fmt::Debug, A: Allocator> fmt::Debug for List<T, A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_list().entries(self).finish()
     }
@@ -139,15 +299,15 @@ impl<T> Default for List<T> {
     }
 }
 
-impl<T> Drop for List<T> {
+impl<T, A: Allocator> Drop for List<T, A> {
     fn drop(&mut self) {
         self.clear();
     }
 }
 
 macro_rules! exact_size_iter_impl {
-    ($type: ty) => {
-        impl<'a, T> ExactSizeIterator for $type {
+    ([$($generics: tt)*] $type: ty) => {
+        impl<$($generics)*> ExactSizeIterator for $type {
             fn len(&self) -> usize {
                 self.size_hint().0
             }
@@ -161,11 +321,11 @@ macro_rules! exact_size_iter_impl {
 
 /// An iterator that yields shared references to the elements of a list.
 #[must_use = "iterators are lazy and do nothing unless consumed"]
-pub struct Iter<'a, T> {
-    next: Option<&'a Node<T>>,
+pub struct Iter<'a, T, A: Allocator = Global> {
+    next: Option<&'a Node<T, A>>,
 }
 
-impl<'a, T> Iterator for Iter<'a, T> {
+impl<'a, T, A: Allocator> Iterator for Iter<'a, T, A> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -186,17 +346,17 @@ impl<'a, T> Iterator for Iter<'a, T> {
     }
 }
 
-impl<'a, T> FusedIterator for Iter<'a, T> {}
+impl<T, A: Allocator> FusedIterator for Iter<'_, T, A> {}
 
-exact_size_iter_impl!{Iter<'a, T>}
+exact_size_iter_impl!{['a, T, A: Allocator] Iter<'a, T, A>}
 
 /// An iterator that yields mutable references to the elements of a list.
 #[must_use = "iterators are lazy and do nothing unless consumed"]
-pub struct IterMut<'a, T> {
-    next: Option<&'a mut Node<T>>,
+pub struct IterMut<'a, T, A: Allocator = Global> {
+    next: Option<&'a mut Node<T, A>>,
 }
 
-impl<'a, T> Iterator for IterMut<'a, T> {
+impl<'a, T, A: Allocator> Iterator for IterMut<'a, T, A> {
     type Item = &'a mut T;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -213,17 +373,71 @@ impl<'a, T> Iterator for IterMut<'a, T> {
     }
 }
 
-impl<'a, T> FusedIterator for IterMut<'a, T> {}
+impl<T, A: Allocator> FusedIterator for IterMut<'_, T, A> {}
+
+exact_size_iter_impl!{['a, T, A: Allocator] IterMut<'a, T, A>}
 
-exact_size_iter_impl!{IterMut<'a, T>}
+/// A node that has been removed from a list, but not deallocated.
+///
+/// It is created by [`List::pop_node`] or [`DetachedNode::new`],
+/// and can be pushed onto a list with [`List::push_node`].
+pub struct DetachedNode<T, A: Allocator = Global> {
+    // `next` is always `None`.
+    node: Box<Node<T, A>, A>,
+}
+
+impl<T> DetachedNode<T> {
+    /// Allocates a new node holding `elem`.
+    pub fn new(elem: T) -> Self {
+        DetachedNode { node: Box::new_in(Node { elem, next: None }, Global) }
+    }
+}
+
+impl<T, A: Allocator> DetachedNode<T, A> {
+    /// Allocates a new node holding `elem` in `alloc`.
+    #[cfg(feature = "allocator_api")]
+    pub fn new_in(elem: T, alloc: A) -> Self {
+        DetachedNode { node: Box::new_in(Node { elem, next: None }, alloc) }
+    }
+
+    /// Replaces the element in the node, returning the old element.
+    pub fn replace(&mut self, elem: T) -> T {
+        core::mem::replace(&mut self.node.elem, elem)
+    }
+
+    /// Deallocates the node, returning its element.
+    pub fn into_inner(self) -> T {
+        Box::into_inner(self.node).elem
+    }
+}
+
+impl<T, A: Allocator> Deref for DetachedNode<T, A> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.node.elem
+    }
+}
+
+impl<T, A: Allocator> DerefMut for DetachedNode<T, A> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.node.elem
+    }
+}
+
+impl<T: fmt::Debug, A: Allocator> fmt::Debug for DetachedNode<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DetachedNode").field(&self.node.elem).finish()
+    }
+}
 
 /// An iterator that consumes a list and yields its elements.
 #[must_use = "iterators are lazy and do nothing unless consumed"]
-pub struct IntoIter<T> {
-    list: List<T>,
+pub struct IntoIter<T, A: Allocator = Global> {
+    list: List<T, A>,
 }
 
-impl<T> Iterator for IntoIter<T> {
+impl<T, A: Allocator> Iterator for IntoIter<T, A> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -235,9 +449,75 @@ impl<T> Iterator for IntoIter<T> {
     }
 }
 
-impl<T> FusedIterator for IntoIter<T> {}
+impl<T, A: Allocator> FusedIterator for IntoIter<T, A> {}
+
+exact_size_iter_impl!{[T, A: Allocator] IntoIter<T, A>}
+
+#[cfg(feature = "rayon")]
+mod rayon_impls {
+    use super::{Allocator, List};
+    use alloc::vec::Vec;
+    use rayon::iter::{FromParallelIterator, IntoParallelIterator, ParallelExtend, ParallelIterator};
+
+    par_iter!{
+        /// A parallel iterator that yields shared references to the elements of a list.
+        ParIter<'a, T>, &'a T, Sync
+    }
+
+    par_iter!{
+        /// A parallel iterator that yields mutable references to the elements of a list.
+        ParIterMut<'a, T>, &'a mut T, Send
+    }
+
+    par_iter!{
+        /// A parallel iterator that consumes a list and yields its elements.
+        IntoParIter<T>, T, Send
+    }
+
+    impl<'a, T: Sync, A: Allocator> IntoParallelIterator for &'a List<T, A> {
+        type Item = &'a T;
+        type Iter = ParIter<'a, T>;
 
-exact_size_iter_impl!{IntoIter<T>}
+        fn into_par_iter(self) -> ParIter<'a, T> {
+            ParIter { inner: self.iter().collect::<Vec<_>>().into_par_iter() }
+        }
+    }
+
+    impl<'a, T: Send, A: Allocator> IntoParallelIterator for &'a mut List<T, A> {
+        type Item = &'a mut T;
+        type Iter = ParIterMut<'a, T>;
+
+        fn into_par_iter(self) -> ParIterMut<'a, T> {
+            ParIterMut { inner: self.iter_mut().collect::<Vec<_>>().into_par_iter() }
+        }
+    }
+
+    impl<T: Send, A: Allocator> IntoParallelIterator for List<T, A> {
+        type Item = T;
+        type Iter = IntoParIter<T>;
+
+        fn into_par_iter(self) -> IntoParIter<T> {
+            IntoParIter { inner: self.into_iter().collect::<Vec<_>>().into_par_iter() }
+        }
+    }
+
+    impl<T: Send, A: Allocator + Clone> ParallelExtend<T> for List<T, A> {
+        /// Pushes each element of the iterator onto the list, in the iterator's order.
+        fn par_extend<I: IntoParallelIterator<Item = T>>(&mut self, par_iter: I) {
+            self.extend(par_iter.into_par_iter().collect::<Vec<_>>());
+        }
+    }
+
+    impl<T: Send> FromParallelIterator<T> for List<T> {
+        /// Creates a list by pushing each element of the iterator onto it, in the iterator's order.
+        fn from_par_iter<I: IntoParallelIterator<Item = T>>(par_iter: I) -> Self {
+            par_iter.into_par_iter().collect::<Vec<_>>().into_iter().collect()
+        }
+    }
+}
+
+#[cfg(feature = "rayon")]
+pub use rayon_impls::{IntoParIter, ParIter, ParIterMut};
 
 #[allow(dead_code)]
 fn assert_properties() {
@@ -254,6 +534,7 @@ fn assert_properties() {
     fn is_thread_safe<T: Send + Sync>() {}
 
     is_thread_safe::<List<i32>>();
+    is_thread_safe::<DetachedNode<i32>>();
     is_thread_safe::<IntoIter<i32>>();
     is_thread_safe::<Iter<i32>>();
     is_thread_safe::<IterMut<i32>>();
@@ -261,8 +542,12 @@ fn assert_properties() {
 
 #[cfg(test)]
 mod tests {
-    use super::List;
+    use super::{DetachedNode, List};
+    use crate::model;
+    use alloc::boxed::Box;
+    use alloc::collections::VecDeque;
     use alloc::vec;
+    use alloc::vec::Vec;
 
     #[test]
     fn push_pop() {
@@ -438,4 +723,266 @@ mod tests {
         assert_eq!(list.pop(), Some(1));
         assert_eq!(list.pop(), None);
     }
+
+    #[test]
+    #[cfg(feature = "allocator_api")]
+    fn custom_allocator() {
+        let counter = Counter::default();
+
+        let mut list = List::new_in(&counter);
+        list.extend([1, 2, 3]);
+        assert_eq!(counter.live.get(), 3);
+
+        assert_eq!(list.pop(), Some(3));
+        assert_eq!(counter.live.get(), 2);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), [2, 1]);
+
+        drop(list);
+        assert_eq!(counter.live.get(), 0);
+        assert_eq!(counter.total.get(), 3);
+    }
+
+    #[test]
+    #[cfg(feature = "allocator_api")]
+    fn move_between_allocators() {
+        let first = Counter::default();
+        let second = Counter::default();
+
+        let mut list = List::new_in(&first);
+        let mut other = List::new_in(&second);
+        list.extend([1, 2]);
+        other.push(3);
+        assert!(list.move_top_to(&mut other));
+        other.push_node(list.pop_node().unwrap());
+        assert!(other.iter().eq(&[1, 2, 3]));
+
+        // Each node is deallocated by the allocator it was allocated with.
+        drop(list);
+        assert_eq!(first.live.get(), 2);
+        assert_eq!(other.pop(), Some(1));
+        assert_eq!((first.live.get(), second.live.get()), (1, 1));
+        drop(other);
+        assert_eq!((first.live.get(), second.live.get()), (0, 0));
+    }
+
+    #[test]
+    fn move_nodes() {
+        let mut list: List<i32> = (0..3).collect();
+        let mut other = List::new();
+        assert!(list.move_top_to(&mut other));
+
+        let mut node = list.pop_node().unwrap();
+        assert_eq!(node.replace(10), 1);
+        other.push_node(node);
+        assert!(other.iter().eq(&[10, 2]));
+        assert!(list.iter().eq(&[0]));
+
+        // Dropping a detached node deallocates it.
+        drop(list.pop_node());
+        assert!(list.pop_node().is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "allocator_api")]
+    fn pop_push_node() {
+        let counter = Counter::default();
+
+        let mut list = List::new_in(&counter);
+        list.extend([1, 2]);
+        let mut node = list.pop_node().unwrap();
+        assert_eq!(node.replace(3), 2);
+        list.push_node(node);
+        assert_eq!(counter.total.get(), 2);
+
+        let mut other = List::new_in(&counter);
+        other.push_node(list.pop_node().unwrap());
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), [1]);
+        assert_eq!(other.iter().copied().collect::<Vec<_>>(), [3]);
+        assert!(List::<i32, &Counter>::new_in(&counter).pop_node().is_none());
+
+        drop((list, other));
+        assert_eq!(counter.live.get(), 0);
+    }
+
+    #[test]
+    fn detached_node() {
+        let mut node = DetachedNode::new(1);
+        assert_eq!(*node, 1);
+        *node += 1;
+        assert_eq!(node.into_inner(), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "allocator_api")]
+    fn move_top_to() {
+        let counter = Counter::default();
+
+        let mut list = List::new_in(&counter);
+        let mut other = List::new_in(&counter);
+        list.extend([1, 2]);
+        assert!(list.move_top_to(&mut other));
+        assert!(list.move_top_to(&mut other));
+        assert!(!list.move_top_to(&mut other));
+
+        assert!(list.is_empty());
+        assert_eq!(other.iter().copied().collect::<Vec<_>>(), [1, 2]);
+        assert_eq!(counter.total.get(), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "allocator_api")]
+    fn try_push_extend() {
+        let counter = Counter { limit: Some(3), ..Counter::default() };
+
+        let mut list = List::new_in(&counter);
+        assert_eq!(list.try_push(1), Ok(()));
+        assert_eq!(list.try_extend([2, 3, 4, 5]).map_err(crate::AllocError::into_inner), Err(4));
+
+        // The elements before the failed one are still in the list.
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), [3, 2, 1]);
+        assert_eq!(list.try_push(6).unwrap_err().into_inner(), 6);
+
+        drop(list);
+        assert_eq!(counter.live.get(), 0);
+    }
+
+    #[test]
+    fn try_from_iter() {
+        let list = List::try_from_iter([1, 2]).unwrap();
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), [2, 1]);
+    }
+
+    impl model::ModelList for List<i32> {
+        fn push(&mut self, elem: i32) {
+            List::push(self, elem);
+        }
+
+        fn pop(&mut self) -> Option<i32> {
+            List::pop(self)
+        }
+
+        fn peek(&self) -> Option<&i32> {
+            List::peek(self)
+        }
+
+        fn peek_mut(&mut self) -> Option<&mut i32> {
+            List::peek_mut(self)
+        }
+
+        fn extend(&mut self, elems: &[i32]) {
+            Extend::extend(self, elems);
+        }
+
+        fn clear(&mut self) {
+            List::clear(self);
+        }
+
+        fn len(&self) -> usize {
+            List::len(self)
+        }
+
+        fn iter(&self) -> impl Iterator<Item = &i32> {
+            List::iter(self)
+        }
+
+        fn iter_mut(&mut self) -> impl Iterator<Item = &mut i32> {
+            List::iter_mut(self)
+        }
+
+        fn into_vec(self) -> Vec<i32> {
+            self.into_iter().collect()
+        }
+    }
+
+    #[test]
+    fn model() {
+        model::check::<List<i32>>();
+    }
+
+    #[test]
+    fn conversions() {
+        let list = List::from(vec![1, 2, 3]);
+        assert!(list.iter().eq(&[1, 2, 3]));
+        assert_eq!(Vec::from(list), [1, 2, 3]);
+
+        let list = List::from(VecDeque::from([1, 2, 3]));
+        assert_eq!(list.peek(), Some(&1));
+        assert_eq!(VecDeque::from(list), [1, 2, 3]);
+
+        let list = List::from(Box::<[i32]>::from([1, 2, 3]));
+        assert_eq!(&*Box::<[i32]>::from(list), [1, 2, 3]);
+
+        #[cfg(feature = "allocator_api")]
+        {
+            let mut list = List::new_in(allocator_api2::alloc::Global);
+            list.push(1);
+            list.push(2);
+            assert_eq!(Vec::from(list), [2, 1]);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn par_iter() {
+        use rayon::prelude::*;
+
+        let mut list: List<u64> = (0..10_000).collect();
+        assert_eq!(list.par_iter().sum::<u64>(), list.iter().sum::<u64>());
+        assert_eq!(
+            list.par_iter().map(|elem| elem * 2).collect::<Vec<_>>(),
+            list.iter().map(|elem| elem * 2).collect::<Vec<_>>(),
+        );
+
+        list.par_iter_mut().for_each(|elem| *elem += 1);
+        assert!(list.iter().copied().eq((1..=10_000).rev()));
+
+        let expected: Vec<u64> = list.iter().copied().collect();
+        assert_eq!(list.into_par_iter().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn par_collect_extend() {
+        use rayon::prelude::*;
+
+        let list: List<u32> = (0..1000).into_par_iter().collect();
+        assert!(list.iter().eq(&(0..1000).collect::<List<u32>>()));
+
+        let mut list = List::new();
+        list.push(0);
+        list.par_extend((1..1000).into_par_iter());
+        assert!(list.iter().copied().eq((0..1000).rev()));
+    }
+
+    /// An allocator that counts its allocations, and fails once `limit` are live.
+    #[cfg(feature = "allocator_api")]
+    #[derive(Default)]
+    struct Counter {
+        live: core::cell::Cell<usize>,
+        total: core::cell::Cell<usize>,
+        limit: Option<usize>,
+    }
+
+    // SAFETY: All allocations are forwarded to `Global`.
+    #[cfg(feature = "allocator_api")]
+    #[allow(unsafe_code)]
+    unsafe impl allocator_api2::alloc::Allocator for Counter {
+        fn allocate(
+            &self,
+            layout: core::alloc::Layout,
+        ) -> Result<core::ptr::NonNull<[u8]>, allocator_api2::alloc::AllocError> {
+            if self.limit == Some(self.live.get()) {
+                return Err(allocator_api2::alloc::AllocError);
+            }
+            self.live.set(self.live.get() + 1);
+            self.total.set(self.total.get() + 1);
+            allocator_api2::alloc::Global.allocate(layout)
+        }
+
+        unsafe fn deallocate(&self, ptr: core::ptr::NonNull<u8>, layout: core::alloc::Layout) {
+            self.live.set(self.live.get() - 1);
+            // SAFETY: `ptr` was allocated by `Global` in `allocate`.
+            unsafe { allocator_api2::alloc::Global.deallocate(ptr, layout) }
+        }
+    }
 }
@@ -0,0 +1,265 @@
+//! [`proptest`] strategies for the lists in this crate.
+//!
+//! Each strategy generates a list from a `Vec` of elements, so that iterating over the list
+//! yields the elements in the same order as the `Vec` they were generated as.
+//! The lists also implement [`Arbitrary`],
+//! using these strategies with `T`'s own strategy.
+use alloc::vec::Vec;
+use ::proptest::arbitrary::{any_with, Arbitrary};
+use ::proptest::collection::{vec, SizeRange, VecStrategy};
+use ::proptest::strategy::{Map, Strategy};
+#[cfg(any(feature = "persistent", feature = "persistent_arc"))]
+use ::proptest::{arbitrary::any, sample::Index};
+
+/// Returns a strategy that generates a [`stack::List`](crate::stack::List)
+/// with a length in `size`, whose elements are generated by `element`.
+#[cfg(feature = "stack")]
+pub fn stack<S: Strategy>(element: S, size: impl Into<SizeRange>) -> impl Strategy<Value = crate::stack::List<S::Value>> {
+    vec(element, size).prop_map(crate::stack::List::from)
+}
+
+#[cfg(feature = "stack")]
+impl<T: Arbitrary> Arbitrary for crate::stack::List<T> {
+    type Parameters = (SizeRange, T::Parameters);
+    type Strategy = Map<VecStrategy<T::Strategy>, fn(Vec<T>) -> Self>;
+
+    fn arbitrary_with((size, params): Self::Parameters) -> Self::Strategy {
+        vec(any_with::<T>(params), size).prop_map(Self::from)
+    }
+}
+
+#[cfg(any(feature = "persistent", feature = "persistent_arc"))]
+macro_rules! persistent_strategies {
+    ($module: ident, $list: ident, $shared: ident) => {
+        #[doc = concat!("Returns a strategy that generates a [`", stringify!($module), "::List`](crate::", stringify!($module), "::List)")]
+        /// with a length in `size`, whose elements are generated by `element`.
+        pub fn $list<S: Strategy>(element: S, size: impl Into<SizeRange>) -> impl Strategy<Value = crate::$module::List<S::Value>> {
+            vec(element, size).prop_map(<crate::$module::List<_>>::from)
+        }
+
+        #[doc = concat!("Returns a strategy that generates `count` [`", stringify!($module), "::List`](crate::", stringify!($module), "::List)s")]
+        /// which share their tails.
+        ///
+        /// A base list is generated with a length in `size`.
+        /// Each list is made by removing some of the base list's first elements,
+        /// and then prepending up to `size` new elements to what is left.
+        pub fn $shared<S>(
+            element: S,
+            size: impl Into<SizeRange>,
+            count: impl Into<SizeRange>,
+        ) -> impl Strategy<Value = Vec<crate::$module::List<S::Value>>>
+        where
+            S: Strategy + Clone,
+        {
+            let size = size.into();
+            let branches = vec((any::<Index>(), vec(element.clone(), size.clone())), count);
+
+            ($list(element, size), branches).prop_map(|(base, branches)| {
+                let base_len = base.len();
+                branches.into_iter().map(|(removed, prefix)| {
+                    let mut list = base.clone();
+                    for _ in 0..removed.index(base_len + 1) {
+                        list = list.tail();
+                    }
+                    prefix.into_iter().rev().fold(list, |list, elem| list.prepend(elem))
+                }).collect()
+            })
+        }
+
+        impl<T: Arbitrary> Arbitrary for crate::$module::List<T> {
+            type Parameters = (SizeRange, T::Parameters);
+            type Strategy = Map<VecStrategy<T::Strategy>, fn(Vec<T>) -> Self>;
+
+            fn arbitrary_with((size, params): Self::Parameters) -> Self::Strategy {
+                vec(any_with::<T>(params), size).prop_map(Self::from)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "persistent")]
+persistent_strategies!{persistent, persistent, persistent_shared}
+
+#[cfg(feature = "persistent_arc")]
+persistent_strategies!{persistent_arc, persistent_arc, persistent_arc_shared}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use ::proptest::prelude::*;
+
+    #[cfg(feature = "stack")]
+    #[derive(Clone, Debug)]
+    enum Op {
+        Push(i32),
+        Pop,
+        Peek,
+        Len,
+    }
+
+    #[cfg(feature = "stack")]
+    fn op() -> impl Strategy<Value = Op> {
+        prop_oneof![
+            any::<i32>().prop_map(Op::Push),
+            Just(Op::Pop),
+            Just(Op::Peek),
+            Just(Op::Len),
+        ]
+    }
+
+    #[cfg(feature = "stack")]
+    proptest! {
+        #[test]
+        fn stack_matches_vec(
+            mut list in super::stack(any::<i32>(), 0..16),
+            ops in prop::collection::vec(op(), 0..64),
+        ) {
+            // The top of the stack is the end of the `Vec`.
+            let mut model: Vec<i32> = list.iter().copied().collect();
+            model.reverse();
+
+            for op in ops {
+                match op {
+                    Op::Push(elem) => {
+                        list.push(elem);
+                        model.push(elem);
+                    }
+                    Op::Pop => prop_assert_eq!(list.pop(), model.pop()),
+                    Op::Peek => prop_assert_eq!(list.peek(), model.last()),
+                    Op::Len => prop_assert_eq!(list.len(), model.len()),
+                }
+            }
+            prop_assert!(list.iter().eq(model.iter().rev()));
+        }
+
+        #[test]
+        fn stack_strategy_keeps_order(elems in prop::collection::vec(any::<u8>(), 0..16)) {
+            let list = crate::stack::List::from(elems.clone());
+            prop_assert!(list.iter().eq(&elems));
+        }
+
+        #[test]
+        fn stack_arbitrary(list in any::<crate::stack::List<u8>>()) {
+            prop_assert_eq!(list.iter().count(), list.len());
+        }
+    }
+
+    #[cfg(any(feature = "persistent", feature = "persistent_arc"))]
+    #[derive(Clone, Debug)]
+    enum PersistentOp {
+        Prepend(i32),
+        Tail,
+        Head,
+        Len,
+        PushFront(i32),
+        PopFront,
+        /// Goes back to an earlier version of the list.
+        Restore(prop::sample::Index),
+    }
+
+    #[cfg(any(feature = "persistent", feature = "persistent_arc"))]
+    fn persistent_op() -> impl Strategy<Value = PersistentOp> {
+        prop_oneof![
+            any::<i32>().prop_map(PersistentOp::Prepend),
+            Just(PersistentOp::Tail),
+            Just(PersistentOp::Head),
+            Just(PersistentOp::Len),
+            any::<i32>().prop_map(PersistentOp::PushFront),
+            Just(PersistentOp::PopFront),
+            any::<prop::sample::Index>().prop_map(PersistentOp::Restore),
+        ]
+    }
+
+    /// Checks a persistent list generated by `$strategy` against a `Vec`,
+    /// whose first element is the front of the list.
+    ///
+    /// Every version made by `prepend` or `tail` is kept, and checked again at the end,
+    /// so changing one version in place must not change the others.
+    #[cfg(any(feature = "persistent", feature = "persistent_arc"))]
+    macro_rules! persistent_matches_vec {
+        ($name: ident, $strategy: expr) => {
+            proptest! {
+                #[test]
+                fn $name(list in $strategy, ops in prop::collection::vec(persistent_op(), 0..64)) {
+                    let mut model: Vec<i32> = list.iter().copied().collect();
+                    let mut list = list;
+                    let mut versions = Vec::new();
+
+                    for op in ops {
+                        match op {
+                            PersistentOp::Prepend(elem) => {
+                                versions.push((list.clone(), model.clone()));
+                                list = list.prepend(elem);
+                                model.insert(0, elem);
+                            }
+                            PersistentOp::Tail => {
+                                versions.push((list.clone(), model.clone()));
+                                list = list.tail();
+                                if !model.is_empty() {
+                                    model.remove(0);
+                                }
+                            }
+                            PersistentOp::Head => prop_assert_eq!(list.head(), model.first()),
+                            PersistentOp::Len => prop_assert_eq!(list.len(), model.len()),
+                            PersistentOp::PushFront(elem) => {
+                                list.push_front(elem);
+                                model.insert(0, elem);
+                            }
+                            PersistentOp::PopFront => {
+                                let expected = (!model.is_empty()).then(|| model.remove(0));
+                                prop_assert_eq!(list.pop_front(), expected);
+                            }
+                            PersistentOp::Restore(index) => {
+                                if !versions.is_empty() {
+                                    (list, model) = index.get(&versions).clone();
+                                }
+                            }
+                        }
+                    }
+
+                    prop_assert!(list.iter().eq(&model));
+                    for (list, model) in &versions {
+                        prop_assert!(list.iter().eq(model));
+                    }
+                }
+            }
+        };
+    }
+
+    /// Generates a different number every time.
+    #[cfg(feature = "persistent")]
+    fn unique() -> impl Strategy<Value = usize> + Clone {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+
+        any::<()>().prop_map(|()| NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+
+    #[cfg(feature = "persistent")]
+    proptest! {
+        #[test]
+        fn persistent_shared_tails(lists in super::persistent_shared(unique(), 1..8, 2..6)) {
+            // Every element is distinct, so two lists that hold the same element
+            // must share the node it is in, and every node after it.
+            for list in &lists {
+                for other in &lists {
+                    let mut tail = list.clone();
+                    while let Some(head) = tail.head() {
+                        if let Some(start) = other.iter().position(|elem| elem == head) {
+                            let nodes = tail.iter().map(core::ptr::from_ref);
+                            prop_assert!(nodes.eq(other.iter().skip(start).map(core::ptr::from_ref)));
+                            break;
+                        }
+                        tail = tail.tail();
+                    }
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "persistent")]
+    persistent_matches_vec!{persistent_matches_vec, super::persistent(any::<i32>(), 0..16)}
+
+    #[cfg(feature = "persistent_arc")]
+    persistent_matches_vec!{persistent_arc_matches_vec, any::<crate::persistent_arc::List<i32>>()}
+}
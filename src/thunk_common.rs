@@ -0,0 +1,102 @@
+//! Memoized values for the lazily evaluated lists.
+//!
+//! A `Thunk` holds either a value, or the pending work that computes it.
+//! The work is run at most once, the first time the value is forced,
+//! and every later force returns the same value.
+
+/// A thunk for the lists that use `Rc`, which is not thread safe.
+#[cfg(any(feature = "persistent_queue", feature = "lazy"))]
+pub(crate) mod rc {
+    use core::cell::{Cell, OnceCell};
+
+    /// A value of type `V` that is computed from a `P` at most once.
+    pub(crate) struct Thunk<V, P> {
+        value: OnceCell<V>,
+        pending: Cell<Option<P>>,
+    }
+
+    impl<V, P> Thunk<V, P> {
+        pub(crate) fn new(pending: P) -> Self {
+            Thunk {
+                value: OnceCell::new(),
+                pending: Cell::new(Some(pending)),
+            }
+        }
+
+        #[cfg(feature = "persistent_queue")]
+        pub(crate) fn ready(value: V) -> Self {
+            Thunk {
+                value: OnceCell::from(value),
+                pending: Cell::new(None),
+            }
+        }
+
+        /// Gets the value, computing it with `run` if needed.
+        pub(crate) fn force(&self, run: impl FnOnce(P) -> V) -> &V {
+            self.value.get_or_init(|| {
+                let pending = self.pending.take().expect("a lazy value depends on itself");
+                run(pending)
+            })
+        }
+
+        /// Gets the value, if it has been computed.
+        #[cfg(feature = "lazy")]
+        pub(crate) fn get(&self) -> Option<&V> {
+            self.value.get()
+        }
+
+        pub(crate) fn into_parts(self) -> (Option<V>, Option<P>) {
+            (self.value.into_inner(), self.pending.into_inner())
+        }
+    }
+}
+
+/// A thunk for the lists that use `Arc`, which is thread safe.
+///
+/// If two threads force the same thunk at once, one of them computes the value
+/// while the other spins until it is done.
+#[cfg(any(feature = "persistent_queue_arc", feature = "lazy_arc"))]
+pub(crate) mod arc {
+    use spin::{Mutex, Once};
+
+    /// A value of type `V` that is computed from a `P` at most once.
+    pub(crate) struct Thunk<V, P> {
+        value: Once<V>,
+        pending: Mutex<Option<P>>,
+    }
+
+    impl<V, P> Thunk<V, P> {
+        pub(crate) fn new(pending: P) -> Self {
+            Thunk {
+                value: Once::new(),
+                pending: Mutex::new(Some(pending)),
+            }
+        }
+
+        #[cfg(feature = "persistent_queue_arc")]
+        pub(crate) fn ready(value: V) -> Self {
+            Thunk {
+                value: Once::initialized(value),
+                pending: Mutex::new(None),
+            }
+        }
+
+        /// Gets the value, computing it with `run` if needed.
+        pub(crate) fn force(&self, run: impl FnOnce(P) -> V) -> &V {
+            self.value.call_once(|| {
+                let pending = self.pending.lock().take().expect("a lazy value depends on itself");
+                run(pending)
+            })
+        }
+
+        /// Gets the value, if it has been computed.
+        #[cfg(feature = "lazy_arc")]
+        pub(crate) fn get(&self) -> Option<&V> {
+            self.value.get()
+        }
+
+        pub(crate) fn into_parts(self) -> (Option<V>, Option<P>) {
+            (self.value.try_into_inner(), self.pending.into_inner())
+        }
+    }
+}
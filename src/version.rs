@@ -0,0 +1,735 @@
+use core::fmt;
+use core::str::FromStr;
+
+/// A struct describing the version of a module in this crate.
+///
+/// List-containing modules in this crate have their own version, separate from the full crate version.
+/// A module on a `0.x.y` version is subject to ANY change during a crate *minor* release,
+/// including removal of it entirely.
+///
+/// This system also exists so that you can use a breaking *crate-level* update,
+/// while knowing that you will not have to change any of your code,
+/// because the update only broke a specific list that you are not using.
+///
+/// Essentially, when using this crate, you should always use an exact version specifier `=x.y.z`,
+/// and only update when you have checked the new `ListVersion` of the list(s) you are using.
+/// The `ListVersion` of all of the lists is next to the module name in the README, and in `module::VERSION`.
+/// A [`VersionReq`] can check a `ListVersion` for you,
+/// and [`require_version!`](crate::require_version) can check it at compile time.
+///
+/// See the [SemVer specification](https://semver.org) for details on the meaning of
+/// "major", "minor", and "patch".
+///
+/// Versions are ordered by their major, then minor, then patch numbers.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ListVersion {
+    /// The major version of a module.
+    ///
+    /// When this is 0, all items in the module are considered "experimental",
+    /// and may be removed at any crate minor release.
+    /// Otherwise, the biggest change to *any* module will cause that level of crate update.
+    pub major: u16,
+    /// The minor version of a module.
+    pub minor: u16,
+    /// The patch version of a module.
+    pub patch: u16,
+}
+
+impl ListVersion {
+    /// Creates a new version.
+    pub const fn new(major: u16, minor: u16, patch: u16) -> Self {
+        ListVersion { major, minor, patch }
+    }
+
+    /// Returns whether this list is considered "experimental" or not.
+    ///
+    /// An experimental list can have breaking changes in non-breaking crate releases.
+    #[inline]
+    pub const fn experimental(self) -> bool {
+        self.major == 0
+    }
+
+    /// Returns whether `self` could be used when `other` was the specified version.
+    ///
+    /// This follows Cargo's caret rules, so it is the same as checking `self` against `^other`:
+    /// `1.2.3` is compatible with `1.0.0` but not `2.0.0`,
+    /// `0.2.3` is compatible with `0.2.0` but not `0.1.0`,
+    /// and `0.0.3` is only compatible with itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use linked_lists::ListVersion;
+    ///
+    /// let version = ListVersion {
+    ///     major: 1,
+    ///     minor: 2,
+    ///     patch: 3,
+    /// };
+    ///
+    /// let expected = linked_lists::ListVersion {
+    ///     major: 1,
+    ///     minor: 0,
+    ///     patch: 0
+    /// };
+    ///
+    /// assert!(version.compatible(expected));
+    /// ```
+    pub const fn compatible(self, other: ListVersion) -> bool {
+        Comparator::exact(Op::Caret, other).matches(self)
+    }
+}
+
+impl fmt::Display for ListVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+impl fmt::Debug for ListVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <Self as fmt::Display>::fmt(self, f)
+    }
+}
+
+impl FromStr for ListVersion {
+    type Err = ParseVersionError;
+
+    /// Parses a version like `"1.2.3"`.
+    ///
+    /// All three numbers are required, and there cannot be any whitespace.
+    fn from_str(version: &str) -> Result<Self, ParseVersionError> {
+        let bytes = version.as_bytes();
+        let mut numbers = [0; 3];
+        let mut i = 0;
+        for (n, number) in numbers.iter_mut().enumerate() {
+            if n > 0 {
+                match bytes.get(i) {
+                    Some(b'.') => i += 1,
+                    Some(&c) => return Err(ParseVersionError { kind: ErrorKind::UnexpectedChar(c) }),
+                    None => return Err(ParseVersionError { kind: ErrorKind::UnexpectedEnd }),
+                }
+            }
+            (*number, i) = parse_number(bytes, i)?;
+        }
+
+        match bytes.get(i) {
+            Some(&c) => Err(ParseVersionError { kind: ErrorKind::UnexpectedChar(c) }),
+            None => Ok(ListVersion::new(numbers[0], numbers[1], numbers[2])),
+        }
+    }
+}
+
+/// Fails to compile unless the `VERSION` of a module in this crate matches a [`VersionReq`].
+///
+/// The first argument is the name of the module, and the second is the requirement, as a string literal.
+/// This lets a build break when an update changes a list you use,
+/// instead of having to check each module's version by hand.
+///
+/// # Examples
+///
+#[cfg_attr(feature = "stack", doc = r#"
+```
+linked_lists::require_version!(stack, "^1.4");
+linked_lists::require_version!(stack, ">=1.4, <2");
+```
+
+A version that does not match the requirement is a compile error:
+
+```compile_fail
+linked_lists::require_version!(stack, "^2");
+```
+
+So is an invalid requirement:
+
+```compile_fail
+linked_lists::require_version!(stack, "1.x");
+```
+"#)]
+#[macro_export]
+macro_rules! require_version {
+    ($module: ident, $req: literal) => {
+        const _: () = {
+            let req = match $crate::VersionReq::parse($req) {
+                Ok(req) => req,
+                Err(_) => panic!(concat!("invalid version requirement \"", $req, "\"")),
+            };
+            assert!(
+                req.matches($crate::$module::VERSION),
+                concat!("`linked_lists::", stringify!($module), "::VERSION` does not match \"", $req, "\""),
+            );
+        };
+    };
+}
+
+/// The maximum number of comparators in a [`VersionReq`].
+const MAX_COMPARATORS: usize = 4;
+
+/// A requirement that a [`ListVersion`] can match, using the same syntax as Cargo.
+///
+/// A requirement is up to 4 comma-separated comparators, which must all match.
+/// Each comparator is an operator (`^`, `~`, `=`, `>`, `>=`, `<` or `<=`) followed by
+/// a version whose minor and patch numbers can be left out.
+/// Without an operator, `^` is used.
+///
+/// See [the Cargo book](https://doc.rust-lang.org/cargo/reference/specifying-dependencies.html)
+/// for the meaning of each operator.
+///
+/// # Examples
+///
+/// ```
+/// use linked_lists::{ListVersion, VersionReq};
+///
+/// let req = VersionReq::parse(">=1.2, <1.5").unwrap();
+///
+/// assert!(req.matches(ListVersion { major: 1, minor: 4, patch: 9 }));
+/// assert!(!req.matches(ListVersion { major: 1, minor: 5, patch: 0 }));
+/// ```
+#[derive(Clone, Copy)]
+pub struct VersionReq {
+    comparators: [Comparator; MAX_COMPARATORS],
+    len: usize,
+}
+
+#[derive(Clone, Copy)]
+struct Comparator {
+    op: Op,
+    major: u16,
+    minor: Option<u16>,
+    patch: Option<u16>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Exact,
+    Greater,
+    GreaterEq,
+    Less,
+    LessEq,
+    Tilde,
+    Caret,
+}
+
+/// The error returned when parsing a [`ListVersion`] or [`VersionReq`] fails.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct ParseVersionError {
+    kind: ErrorKind,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum ErrorKind {
+    UnexpectedEnd,
+    UnexpectedChar(u8),
+    LeadingZero,
+    Overflow,
+    TooManyComparators,
+}
+
+// `?` cannot be used in a `const fn`.
+macro_rules! const_try {
+    ($result: expr) => {
+        match $result {
+            Ok(value) => value,
+            Err(err) => return Err(err),
+        }
+    };
+}
+
+impl VersionReq {
+    /// Parses a requirement, like `"^1.2"` or `">=0.3.1, <0.5"`.
+    ///
+    /// # Errors
+    ///
+    /// If `req` is not a valid requirement, or has more than 4 comparators,
+    /// this returns a [`ParseVersionError`].
+    pub const fn parse(req: &str) -> Result<Self, ParseVersionError> {
+        let bytes = req.as_bytes();
+        let mut req = VersionReq {
+            comparators: [Comparator::exact(Op::Exact, ListVersion { major: 0, minor: 0, patch: 0 }); MAX_COMPARATORS],
+            len: 0,
+        };
+
+        let mut i = skip_whitespace(bytes, 0);
+        loop {
+            if req.len == MAX_COMPARATORS {
+                return Err(ParseVersionError { kind: ErrorKind::TooManyComparators });
+            }
+
+            let (comparator, next) = const_try!(Comparator::parse(bytes, i));
+            req.comparators[req.len] = comparator;
+            req.len += 1;
+
+            i = skip_whitespace(bytes, next);
+            if i == bytes.len() {
+                return Ok(req);
+            }
+            if bytes[i] != b',' {
+                return Err(ParseVersionError { kind: ErrorKind::UnexpectedChar(bytes[i]) });
+            }
+            i = skip_whitespace(bytes, i + 1);
+        }
+    }
+
+    /// Returns whether `version` matches every comparator in this requirement.
+    pub const fn matches(&self, version: ListVersion) -> bool {
+        let mut i = 0;
+        while i < self.len {
+            if !self.comparators[i].matches(version) {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+}
+
+impl Comparator {
+    const fn exact(op: Op, version: ListVersion) -> Self {
+        Comparator { op, major: version.major, minor: Some(version.minor), patch: Some(version.patch) }
+    }
+
+    /// Parses a comparator starting at `bytes[i]`, and returns it with the index after it.
+    const fn parse(bytes: &[u8], i: usize) -> Result<(Self, usize), ParseVersionError> {
+        let (op, i) = Op::parse(bytes, i);
+        let i = skip_whitespace(bytes, i);
+
+        let (major, mut i) = const_try!(parse_number(bytes, i));
+        let mut minor = None;
+        let mut patch = None;
+        if i < bytes.len() && bytes[i] == b'.' {
+            let (number, next) = const_try!(parse_number(bytes, i + 1));
+            minor = Some(number);
+            i = next;
+
+            if i < bytes.len() && bytes[i] == b'.' {
+                let (number, next) = const_try!(parse_number(bytes, i + 1));
+                patch = Some(number);
+                i = next;
+            }
+        }
+
+        Ok((Comparator { op, major, minor, patch }, i))
+    }
+
+    const fn matches(&self, version: ListVersion) -> bool {
+        match self.op {
+            Op::Exact => self.matches_exact(version),
+            Op::Greater => self.matches_greater(version),
+            Op::GreaterEq => self.matches_exact(version) || self.matches_greater(version),
+            Op::Less => self.matches_less(version),
+            Op::LessEq => self.matches_exact(version) || self.matches_less(version),
+            Op::Tilde => self.matches_tilde(version),
+            Op::Caret => self.matches_caret(version),
+        }
+    }
+
+    /// Whether `version` is equal to this comparator's version, ignoring the numbers it leaves out.
+    const fn matches_exact(&self, version: ListVersion) -> bool {
+        if version.major != self.major {
+            return false;
+        }
+        if let Some(minor) = self.minor {
+            if version.minor != minor {
+                return false;
+            }
+        }
+        if let Some(patch) = self.patch {
+            if version.patch != patch {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Whether `version` is greater than every version that matches [`Comparator::matches_exact`].
+    const fn matches_greater(&self, version: ListVersion) -> bool {
+        if version.major != self.major {
+            return version.major > self.major;
+        }
+        let Some(minor) = self.minor else { return false };
+        if version.minor != minor {
+            return version.minor > minor;
+        }
+        let Some(patch) = self.patch else { return false };
+        version.patch > patch
+    }
+
+    /// Whether `version` is less than every version that matches [`Comparator::matches_exact`].
+    const fn matches_less(&self, version: ListVersion) -> bool {
+        if version.major != self.major {
+            return version.major < self.major;
+        }
+        let Some(minor) = self.minor else { return false };
+        if version.minor != minor {
+            return version.minor < minor;
+        }
+        let Some(patch) = self.patch else { return false };
+        version.patch < patch
+    }
+
+    const fn matches_tilde(&self, version: ListVersion) -> bool {
+        if version.major != self.major {
+            return false;
+        }
+        if let Some(minor) = self.minor {
+            if version.minor != minor {
+                return false;
+            }
+        }
+        if let Some(patch) = self.patch {
+            if version.patch < patch {
+                return false;
+            }
+        }
+        true
+    }
+
+    const fn matches_caret(&self, version: ListVersion) -> bool {
+        if version.major != self.major {
+            return false;
+        }
+        let Some(minor) = self.minor else { return true };
+        let Some(patch) = self.patch else {
+            // `^1.2` is `>=1.2.0, <2.0.0`, but `^0.2` is `>=0.2.0, <0.3.0`.
+            return if self.major > 0 { version.minor >= minor } else { version.minor == minor };
+        };
+
+        if self.major > 0 {
+            version.minor > minor || (version.minor == minor && version.patch >= patch)
+        } else if minor > 0 {
+            version.minor == minor && version.patch >= patch
+        } else {
+            version.minor == minor && version.patch == patch
+        }
+    }
+}
+
+impl Op {
+    /// Parses an operator starting at `bytes[i]`, and returns it with the index after it.
+    /// If there is no operator, this returns [`Op::Caret`].
+    const fn parse(bytes: &[u8], i: usize) -> (Self, usize) {
+        let next = if i + 1 < bytes.len() { bytes[i + 1] } else { 0 };
+        match if i < bytes.len() { bytes[i] } else { 0 } {
+            b'=' => (Op::Exact, i + 1),
+            b'>' if next == b'=' => (Op::GreaterEq, i + 2),
+            b'>' => (Op::Greater, i + 1),
+            b'<' if next == b'=' => (Op::LessEq, i + 2),
+            b'<' => (Op::Less, i + 1),
+            b'~' => (Op::Tilde, i + 1),
+            b'^' => (Op::Caret, i + 1),
+            _ => (Op::Caret, i),
+        }
+    }
+
+    const fn as_str(self) -> &'static str {
+        match self {
+            Op::Exact => "=",
+            Op::Greater => ">",
+            Op::GreaterEq => ">=",
+            Op::Less => "<",
+            Op::LessEq => "<=",
+            Op::Tilde => "~",
+            Op::Caret => "^",
+        }
+    }
+}
+
+const fn skip_whitespace(bytes: &[u8], mut i: usize) -> usize {
+    while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+        i += 1;
+    }
+    i
+}
+
+/// Parses a version number starting at `bytes[i]`, and returns it with the index after it.
+const fn parse_number(bytes: &[u8], start: usize) -> Result<(u16, usize), ParseVersionError> {
+    let mut i = start;
+    let mut number: u16 = 0;
+    while i < bytes.len() && bytes[i].is_ascii_digit() {
+        let digit = (bytes[i] - b'0') as u16;
+        number = match number.checked_mul(10) {
+            Some(number) => match number.checked_add(digit) {
+                Some(number) => number,
+                None => return Err(ParseVersionError { kind: ErrorKind::Overflow }),
+            },
+            None => return Err(ParseVersionError { kind: ErrorKind::Overflow }),
+        };
+        i += 1;
+    }
+
+    if i == start {
+        return Err(ParseVersionError {
+            kind: if i < bytes.len() { ErrorKind::UnexpectedChar(bytes[i]) } else { ErrorKind::UnexpectedEnd },
+        });
+    }
+    if bytes[start] == b'0' && i - start > 1 {
+        return Err(ParseVersionError { kind: ErrorKind::LeadingZero });
+    }
+    Ok((number, i))
+}
+
+impl fmt::Display for VersionReq {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, comparator) in self.comparators[..self.len].iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}{}", comparator.op.as_str(), comparator.major)?;
+            if let Some(minor) = comparator.minor {
+                write!(f, ".{minor}")?;
+            }
+            if let Some(patch) = comparator.patch {
+                write!(f, ".{patch}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for VersionReq {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <Self as fmt::Display>::fmt(self, f)
+    }
+}
+
+impl fmt::Display for ParseVersionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ErrorKind::UnexpectedEnd => f.write_str("unexpected end of input, expected a version number"),
+            ErrorKind::UnexpectedChar(c) => write!(f, "unexpected character {:?}", char::from(c)),
+            ErrorKind::LeadingZero => f.write_str("version numbers cannot have leading zeros"),
+            ErrorKind::Overflow => write!(f, "version numbers cannot be greater than {}", u16::MAX),
+            ErrorKind::TooManyComparators => write!(f, "requirements cannot have more than {MAX_COMPARATORS} comparators"),
+        }
+    }
+}
+
+impl fmt::Debug for ParseVersionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ParseVersionError").field(&self.kind).finish()
+    }
+}
+
+impl core::error::Error for ParseVersionError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{ErrorKind, ListVersion, ParseVersionError, VersionReq};
+    use alloc::string::ToString;
+
+    const fn v(major: u16, minor: u16, patch: u16) -> ListVersion {
+        ListVersion { major, minor, patch }
+    }
+
+    #[test]
+    fn compatibility() {
+        let experimental = v(0, 1, 4);
+        let less_experimental = v(0, 1, 77);
+        assert!(less_experimental.compatible(experimental));
+
+        let one = v(1, 3, 4);
+        assert!(!one.compatible(experimental));
+
+        let two = v(2, 0, 0);
+        assert!(!one.compatible(two));
+        assert!(!two.compatible(one));
+    }
+
+    #[test]
+    fn compatible_is_caret() {
+        // (version, other, version.compatible(other))
+        let cases = [
+            (v(1, 2, 3), v(1, 2, 3), true),
+            (v(1, 2, 4), v(1, 2, 3), true),
+            (v(1, 3, 0), v(1, 2, 3), true),
+            (v(1, 2, 2), v(1, 2, 3), false),
+            (v(1, 1, 9), v(1, 2, 3), false),
+            (v(2, 0, 5), v(1, 0, 3), false),
+            (v(1, 0, 3), v(2, 0, 5), false),
+            (v(0, 2, 3), v(0, 2, 0), true),
+            (v(0, 3, 0), v(0, 2, 0), false),
+            (v(0, 2, 0), v(0, 2, 1), false),
+            (v(0, 0, 3), v(0, 0, 3), true),
+            (v(0, 0, 4), v(0, 0, 3), false),
+            (v(0, 1, 0), v(0, 0, 3), false),
+            (v(1, 0, 0), v(0, 9, 9), false),
+        ];
+
+        for (version, other, expected) in cases {
+            assert_eq!(version.compatible(other), expected, "{version} compatible with {other}");
+        }
+    }
+
+    #[test]
+    fn matches() {
+        // (requirement, version, expected)
+        let cases = [
+            ("1.2.3", v(1, 2, 3), true),
+            ("1.2.3", v(1, 9, 0), true),
+            ("1.2.3", v(1, 2, 2), false),
+            ("1.2.3", v(2, 0, 0), false),
+            ("^1.2", v(1, 2, 0), true),
+            ("^1.2", v(1, 1, 9), false),
+            ("^1", v(1, 9, 9), true),
+            ("^1", v(2, 0, 0), false),
+            ("^0.2.3", v(0, 2, 9), true),
+            ("^0.2.3", v(0, 3, 0), false),
+            ("^0.2", v(0, 2, 0), true),
+            ("^0.2", v(0, 3, 0), false),
+            ("^0.0.3", v(0, 0, 3), true),
+            ("^0.0.3", v(0, 0, 4), false),
+            ("^0.0", v(0, 0, 7), true),
+            ("^0.0", v(0, 1, 0), false),
+            ("^0", v(0, 9, 0), true),
+            ("^0", v(1, 0, 0), false),
+            ("~1.2.3", v(1, 2, 9), true),
+            ("~1.2.3", v(1, 2, 2), false),
+            ("~1.2.3", v(1, 3, 0), false),
+            ("~1.2", v(1, 2, 0), true),
+            ("~1.2", v(1, 3, 0), false),
+            ("~1", v(1, 9, 0), true),
+            ("~1", v(2, 0, 0), false),
+            ("~0.2", v(0, 2, 5), true),
+            ("=1.2.3", v(1, 2, 3), true),
+            ("=1.2.3", v(1, 2, 4), false),
+            ("=1.2", v(1, 2, 9), true),
+            ("=1.2", v(1, 3, 0), false),
+            ("=1", v(1, 9, 9), true),
+            ("=1", v(2, 0, 0), false),
+            (">1.2.3", v(1, 2, 4), true),
+            (">1.2.3", v(1, 2, 3), false),
+            (">1.2", v(1, 3, 0), true),
+            (">1.2", v(1, 2, 9), false),
+            (">1", v(2, 0, 0), true),
+            (">1", v(1, 9, 9), false),
+            (">=1.2.3", v(1, 2, 3), true),
+            (">=1.2.3", v(1, 2, 2), false),
+            (">=1.2", v(1, 2, 0), true),
+            (">=1.2", v(1, 1, 9), false),
+            (">=1", v(1, 0, 0), true),
+            (">=1", v(0, 9, 9), false),
+            ("<1.2.3", v(1, 2, 2), true),
+            ("<1.2.3", v(1, 2, 3), false),
+            ("<1.2", v(1, 1, 9), true),
+            ("<1.2", v(1, 2, 0), false),
+            ("<1", v(0, 9, 9), true),
+            ("<1", v(1, 0, 0), false),
+            ("<=1.2.3", v(1, 2, 3), true),
+            ("<=1.2.3", v(1, 2, 4), false),
+            ("<=1.2", v(1, 2, 9), true),
+            ("<=1.2", v(1, 3, 0), false),
+            ("<=1", v(1, 9, 9), true),
+            ("<=1", v(2, 0, 0), false),
+            (">=1.2, <1.5", v(1, 4, 9), true),
+            (">=1.2, <1.5", v(1, 5, 0), false),
+            (">=1.2, <1.5", v(1, 1, 0), false),
+            (" >= 0.3.1 ,<0.5 ", v(0, 4, 0), true),
+            (">=0.3.1,<0.5", v(0, 3, 0), false),
+            ("^1.4, ~1.4.2, >1.4.2, <=1.4.5", v(1, 4, 5), true),
+            ("^1.4, ~1.4.2, >1.4.2, <=1.4.5", v(1, 4, 2), false),
+        ];
+
+        for (req, version, expected) in cases {
+            let parsed = VersionReq::parse(req).unwrap();
+            assert_eq!(parsed.matches(version), expected, "{version} matches {req:?}");
+        }
+    }
+
+    #[test]
+    fn from_str() {
+        let cases = [
+            ("1.2.3", Ok(v(1, 2, 3))),
+            ("0.0.0", Ok(v(0, 0, 0))),
+            ("65535.10.0", Ok(v(u16::MAX, 10, 0))),
+            ("", Err(ErrorKind::UnexpectedEnd)),
+            ("1.2", Err(ErrorKind::UnexpectedEnd)),
+            ("1.2.", Err(ErrorKind::UnexpectedEnd)),
+            ("1.2.3.", Err(ErrorKind::UnexpectedChar(b'.'))),
+            ("1.2.3 ", Err(ErrorKind::UnexpectedChar(b' '))),
+            ("^1.2.3", Err(ErrorKind::UnexpectedChar(b'^'))),
+            ("1,2,3", Err(ErrorKind::UnexpectedChar(b','))),
+            ("1.02.3", Err(ErrorKind::LeadingZero)),
+            ("1.2.65536", Err(ErrorKind::Overflow)),
+        ];
+
+        for (version, expected) in cases {
+            let expected = expected.map_err(|kind| ParseVersionError { kind });
+            assert_eq!(version.parse::<ListVersion>(), expected, "{version:?}");
+        }
+    }
+
+    #[test]
+    fn ordering() {
+        let mut versions = [v(1, 0, 0), v(0, 2, 1), v(0, 10, 0), v(1, 0, 10), v(0, 2, 0), v(1, 1, 0)];
+        versions.sort();
+        assert_eq!(versions, [v(0, 2, 0), v(0, 2, 1), v(0, 10, 0), v(1, 0, 0), v(1, 0, 10), v(1, 1, 0)]);
+
+        assert_eq!(v(1, 2, 3), ListVersion::new(1, 2, 3));
+        assert!(v(1, 2, 3) < v(1, 3, 0));
+    }
+
+    #[test]
+    fn const_fns() {
+        const VERSION: ListVersion = ListVersion::new(0, 3, 1);
+
+        const { assert!(VERSION.experimental()) };
+        const { assert!(VERSION.compatible(ListVersion::new(0, 3, 0))) };
+        const { assert!(!VERSION.compatible(ListVersion::new(0, 2, 0))) };
+    }
+
+    #[test]
+    fn parse_errors() {
+        let cases = [
+            ("", ErrorKind::UnexpectedEnd),
+            ("  ", ErrorKind::UnexpectedEnd),
+            ("^", ErrorKind::UnexpectedEnd),
+            ("1.", ErrorKind::UnexpectedEnd),
+            ("1.2,", ErrorKind::UnexpectedEnd),
+            ("1.2.3.4", ErrorKind::UnexpectedChar(b'.')),
+            ("1 2", ErrorKind::UnexpectedChar(b'2')),
+            ("=>1", ErrorKind::UnexpectedChar(b'>')),
+            ("1.x", ErrorKind::UnexpectedChar(b'x')),
+            ("*", ErrorKind::UnexpectedChar(b'*')),
+            ("01.2", ErrorKind::LeadingZero),
+            ("1.02", ErrorKind::LeadingZero),
+            ("65536", ErrorKind::Overflow),
+            ("1, 2, 3, 4, 5", ErrorKind::TooManyComparators),
+        ];
+
+        for (req, kind) in cases {
+            assert_eq!(VersionReq::parse(req).unwrap_err(), ParseVersionError { kind }, "{req:?}");
+        }
+
+        assert!(VersionReq::parse("65535.0.0").is_ok());
+        assert!(VersionReq::parse("1, 2, 3, 4").is_ok());
+    }
+
+    #[test]
+    fn const_parse() {
+        const REQ: VersionReq = match VersionReq::parse(">=1.4, <2") {
+            Ok(req) => req,
+            Err(_) => panic!(),
+        };
+
+        const { assert!(REQ.matches(ListVersion { major: 1, minor: 5, patch: 0 })) };
+        const { assert!(!REQ.matches(ListVersion { major: 2, minor: 0, patch: 0 })) };
+    }
+
+    #[test]
+    fn display() {
+        let cases = [
+            ("1.2.3", "^1.2.3"),
+            (">=1.2 , <1.5", ">=1.2, <1.5"),
+            ("~0", "~0"),
+        ];
+
+        for (req, expected) in cases {
+            assert_eq!(VersionReq::parse(req).unwrap().to_string(), expected);
+        }
+
+        assert_eq!(
+            VersionReq::parse("1.x").unwrap_err().to_string(),
+            "unexpected character 'x'",
+        );
+    }
+}
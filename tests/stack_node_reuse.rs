@@ -0,0 +1,80 @@
+//! Checks that moving nodes between lists, or recycling them, does not allocate.
+#![cfg(feature = "stack")]
+
+use linked_lists::stack::{DetachedNode, List};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// Counts the allocations made by each thread, so other tests' threads don't interfere.
+struct Tracking;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+// SAFETY: All allocations are forwarded to `System`.
+unsafe impl GlobalAlloc for Tracking {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        // SAFETY: The caller upholds `GlobalAlloc::alloc`'s contract.
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: The caller upholds `GlobalAlloc::dealloc`'s contract.
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Tracking = Tracking;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
+
+#[test]
+fn steady_state_push_pop_does_not_allocate() {
+    let mut list: List<u64> = (0..16).collect();
+    let mut spare: Option<DetachedNode<u64>> = None;
+
+    let before = allocations();
+    for i in 0..100_000 {
+        // Alternate between pushing a recycled node and popping one to recycle.
+        match spare.take() {
+            Some(mut node) => {
+                node.replace(i);
+                list.push_node(node);
+            }
+            None => spare = list.pop_node(),
+        }
+    }
+    assert_eq!(allocations(), before);
+
+    assert_eq!(list.len() + usize::from(spare.is_some()), 16);
+}
+
+#[test]
+fn move_top_to_does_not_allocate() {
+    let mut from: List<u64> = (0..1000).collect();
+    let mut to = List::new();
+
+    let before = allocations();
+    while from.move_top_to(&mut to) {}
+    assert_eq!(allocations(), before);
+
+    assert!(to.iter().copied().eq(0..1000));
+}
+
+#[test]
+fn push_allocates_once_per_element() {
+    let mut list = List::new();
+
+    let before = allocations();
+    list.extend(0..100);
+    assert_eq!(allocations() - before, 100);
+
+    while list.pop().is_some() {}
+    list.push_node(DetachedNode::new(0));
+    assert_eq!(allocations() - before, 101);
+}